@@ -0,0 +1,51 @@
+//! zkboost command line tools.
+//!
+//! - `replay`: re-sends proof requests recorded by a server (see `record_dir`) to a baseline and a
+//!   candidate server and diffs the proof outcomes and verification results.
+//! - `report`: summarizes proof results files written by servers (see `results_path`) and compares
+//!   proving time and proof size across runs.
+//! - `queue`: shows the queued and running proofs of a server.
+//! - `records`: lists, shows, checks and prunes recorded proof requests.
+
+#![warn(unused_crate_dependencies)]
+
+use clap::{Parser, Subcommand};
+use tracing_subscriber::EnvFilter;
+
+mod queue;
+mod records;
+mod replay;
+mod report;
+
+#[derive(Parser)]
+#[command(author, version, about, long_about = None)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Replay recorded proof requests against two servers and diff the outcomes.
+    Replay(replay::ReplayArgs),
+    /// Summarize proof results files and compare runs against the first one.
+    Report(report::ReportArgs),
+    /// Show queued and running proofs of a server.
+    Queue(queue::QueueArgs),
+    /// List, show, check and prune recorded proof requests.
+    Records(records::RecordsArgs),
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::from_default_env())
+        .init();
+
+    match Cli::parse().command {
+        Command::Replay(args) => replay::run(args).await,
+        Command::Report(args) => report::run(args),
+        Command::Queue(args) => queue::run(args).await,
+        Command::Records(args) => records::run(args),
+    }
+}
@@ -0,0 +1,36 @@
+//! `zkboost-cli queue`: prints the queued and running proofs of every proving backend of a server.
+
+use clap::Args;
+use url::Url;
+use zkboost_client::{QueuedProof, zkBoostClient};
+
+/// Arguments of the `queue` subcommand.
+#[derive(Debug, Args)]
+pub(crate) struct QueueArgs {
+    /// Endpoint of the server.
+    #[arg(long, default_value = "http://localhost:3000")]
+    endpoint: Url,
+}
+
+pub(crate) async fn run(args: QueueArgs) -> anyhow::Result<()> {
+    let queue = zkBoostClient::new(args.endpoint).get_queue().await?;
+    for queue in queue.queues {
+        println!("{} ({} queued)", queue.proof_type, queue.queued.len());
+        if let Some(running) = &queue.running {
+            println!("  running {}", describe(running));
+        }
+        for queued in &queue.queued {
+            println!("  queued  {}", describe(queued));
+        }
+    }
+    Ok(())
+}
+
+fn describe(proof: &QueuedProof) -> String {
+    format!(
+        "block {} ({}) for {:.1}s",
+        proof.block_number,
+        proof.new_payload_request_root,
+        proof.elapsed_ms as f64 / 1000.0
+    )
+}
@@ -0,0 +1,257 @@
+//! `zkboost-cli records`: lists, shows, checks and prunes proof requests recorded by a server
+//! configured with `record_dir`.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{Context, anyhow, bail, ensure};
+use clap::{Args, Subcommand};
+use zkboost_types::{
+    Decode, Hash256, MainnetEthSpec, NewPayloadRequest, RecordedProofRequest, TreeHash,
+};
+
+/// Arguments of the `records` subcommand.
+#[derive(Debug, Args)]
+pub(crate) struct RecordsArgs {
+    /// Directory with requests recorded by a server configured with `record_dir`.
+    #[arg(long)]
+    dir: PathBuf,
+    #[command(subcommand)]
+    command: RecordsCommand,
+}
+
+#[derive(Debug, Subcommand)]
+enum RecordsCommand {
+    /// List recorded requests, oldest first.
+    Ls,
+    /// Show a recorded request.
+    Show {
+        /// `new_payload_request_root` of the request.
+        root: Hash256,
+    },
+    /// Decode recorded requests and check they match their recorded root, all if none is given.
+    Verify {
+        /// `new_payload_request_root` of the request.
+        root: Option<Hash256>,
+    },
+    /// Delete recorded requests received more than `older_than_hours` ago.
+    Prune {
+        /// Age in hours above which requests are deleted.
+        #[arg(long)]
+        older_than_hours: u64,
+        /// Only print the requests that would be deleted.
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+pub(crate) fn run(args: RecordsArgs) -> anyhow::Result<()> {
+    let dir = &args.dir;
+    match args.command {
+        RecordsCommand::Ls => {
+            for record in load_metadata(dir)? {
+                println!(
+                    "{} {} {}",
+                    record.new_payload_request_root,
+                    record.received_at_ms,
+                    describe_proof_types(&record)
+                );
+            }
+        }
+        RecordsCommand::Show { root } => {
+            let record = read_metadata(&metadata_path(dir, root))?;
+            let new_payload_request = read_body(dir, root)?;
+            println!("root:        {}", record.new_payload_request_root);
+            println!("received at: {} ms", record.received_at_ms);
+            println!("proof types: {}", describe_proof_types(&record));
+            println!("block:       {}", new_payload_request.block_number());
+            println!("block hash:  {}", new_payload_request.block_hash());
+            println!("timestamp:   {}", new_payload_request.timestamp());
+        }
+        RecordsCommand::Verify { root } => {
+            let roots = match root {
+                Some(root) => vec![root],
+                None => load_metadata(dir)?
+                    .into_iter()
+                    .map(|record| record.new_payload_request_root)
+                    .collect(),
+            };
+            let mut invalid = 0;
+            for root in &roots {
+                match verify(dir, *root) {
+                    Ok(()) => println!("{root} ok"),
+                    Err(error) => {
+                        invalid += 1;
+                        println!("{root} INVALID {error:#}");
+                    }
+                }
+            }
+            println!("verified {} records, {invalid} invalid", roots.len());
+            if invalid > 0 {
+                bail!("{invalid} invalid records");
+            }
+        }
+        RecordsCommand::Prune {
+            older_than_hours,
+            dry_run,
+        } => {
+            let now_ms = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis() as u64;
+            let max_age_ms = Duration::from_secs(older_than_hours * 3600).as_millis() as u64;
+            let expired = expired(load_metadata(dir)?, max_age_ms, now_ms);
+            for root in &expired {
+                println!("{root} pruned");
+                if !dry_run {
+                    remove_if_exists(&body_path(dir, *root))?;
+                    remove_if_exists(&metadata_path(dir, *root))?;
+                }
+            }
+            println!("pruned {} records", expired.len());
+        }
+    }
+    Ok(())
+}
+
+/// Checks that the recorded body decodes and hashes to the recorded root.
+fn verify(dir: &Path, root: Hash256) -> anyhow::Result<()> {
+    let record = read_metadata(&metadata_path(dir, root))?;
+    ensure!(
+        record.new_payload_request_root == root,
+        "metadata records root {}",
+        record.new_payload_request_root
+    );
+    ensure!(!record.proof_types.is_empty(), "no proof types recorded");
+    let actual = read_body(dir, root)?.tree_hash_root();
+    ensure!(actual == root, "body hashes to {actual}");
+    Ok(())
+}
+
+/// Loads the metadata of all recorded requests in `dir`, ordered by the time they were received.
+fn load_metadata(dir: &Path) -> anyhow::Result<Vec<RecordedProofRequest>> {
+    let mut records = Vec::new();
+    for entry in fs::read_dir(dir).with_context(|| format!("read {}", dir.display()))? {
+        let path = entry?.path();
+        if path.extension().is_none_or(|extension| extension != "json") {
+            continue;
+        }
+        records.push(read_metadata(&path)?);
+    }
+    records.sort_by_key(|record| record.received_at_ms);
+    Ok(records)
+}
+
+/// Returns the roots of the records received more than `max_age_ms` before `now_ms`.
+fn expired(records: Vec<RecordedProofRequest>, max_age_ms: u64, now_ms: u64) -> Vec<Hash256> {
+    records
+        .into_iter()
+        .filter(|record| now_ms.saturating_sub(record.received_at_ms) > max_age_ms)
+        .map(|record| record.new_payload_request_root)
+        .collect()
+}
+
+fn read_metadata(path: &Path) -> anyhow::Result<RecordedProofRequest> {
+    let metadata = fs::read(path).with_context(|| format!("read {}", path.display()))?;
+    serde_json::from_slice(&metadata).with_context(|| format!("parse {}", path.display()))
+}
+
+fn read_body(dir: &Path, root: Hash256) -> anyhow::Result<NewPayloadRequest<MainnetEthSpec>> {
+    let path = body_path(dir, root);
+    let body = fs::read(&path).with_context(|| format!("read {}", path.display()))?;
+    NewPayloadRequest::from_ssz_bytes(&body)
+        .map_err(|e| anyhow!("decode {}: {e:?}", path.display()))
+}
+
+fn metadata_path(dir: &Path, root: Hash256) -> PathBuf {
+    dir.join(format!("{root}.json"))
+}
+
+fn body_path(dir: &Path, root: Hash256) -> PathBuf {
+    dir.join(format!("{root}.ssz"))
+}
+
+fn remove_if_exists(path: &Path) -> anyhow::Result<()> {
+    match fs::remove_file(path) {
+        Err(error) if error.kind() != std::io::ErrorKind::NotFound => {
+            Err(error).with_context(|| format!("remove {}", path.display()))
+        }
+        _ => Ok(()),
+    }
+}
+
+fn describe_proof_types(record: &RecordedProofRequest) -> String {
+    record
+        .proof_types
+        .iter()
+        .map(|proof_type| proof_type.as_str())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::Path};
+
+    use zkboost_types::{
+        Decode, Hash256, MainnetEthSpec, NewPayloadRequest, ProofType, RecordedProofRequest,
+        TreeHash,
+    };
+
+    use super::{body_path, expired, load_metadata, metadata_path, verify};
+
+    const NEW_PAYLOAD_REQUEST: &[u8] =
+        include_bytes!("../../server/tests/fixture/new_payload_request.ssz");
+
+    fn write_record(dir: &Path, root: Hash256, received_at_ms: u64, body: &[u8]) {
+        let record = RecordedProofRequest {
+            new_payload_request_root: root,
+            proof_types: vec![ProofType::RethZisk],
+            received_at_ms,
+        };
+        fs::write(
+            metadata_path(dir, root),
+            serde_json::to_vec(&record).unwrap(),
+        )
+        .unwrap();
+        fs::write(body_path(dir, root), body).unwrap();
+    }
+
+    #[test]
+    fn test_list_and_filter() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let dir = tempdir.path();
+        let root = NewPayloadRequest::<MainnetEthSpec>::from_ssz_bytes(NEW_PAYLOAD_REQUEST)
+            .unwrap()
+            .tree_hash_root();
+        write_record(dir, root, 3_000, NEW_PAYLOAD_REQUEST);
+        write_record(dir, Hash256::repeat_byte(1), 1_000, b"garbage");
+        write_record(dir, Hash256::repeat_byte(2), 2_000, NEW_PAYLOAD_REQUEST);
+        // Files other than metadata are not listed.
+        fs::write(dir.join("notes.txt"), "").unwrap();
+
+        let records = load_metadata(dir).unwrap();
+        assert_eq!(
+            records
+                .iter()
+                .map(|record| record.new_payload_request_root)
+                .collect::<Vec<_>>(),
+            [Hash256::repeat_byte(1), Hash256::repeat_byte(2), root]
+        );
+        assert_eq!(
+            expired(records, 1_500, 4_000),
+            [Hash256::repeat_byte(1), Hash256::repeat_byte(2)]
+        );
+
+        verify(dir, root).unwrap();
+        // Undecodable body.
+        assert!(verify(dir, Hash256::repeat_byte(1)).is_err());
+        // Body hashing to another root.
+        assert!(verify(dir, Hash256::repeat_byte(2)).is_err());
+        // Missing record.
+        assert!(verify(dir, Hash256::repeat_byte(3)).is_err());
+    }
+}
@@ -0,0 +1,240 @@
+//! `zkboost-cli replay`: re-sends recorded proof requests to a baseline and a candidate server,
+//! then compares proof outcomes and cross-verifies the proofs on both servers.
+//!
+//! Requests are sent one after another, back-to-back or at their recorded pace with `--speed`.
+
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use anyhow::{Context, anyhow, bail, ensure};
+use bytes::Bytes;
+use clap::Args;
+use tokio::time::{Instant, sleep_until};
+use tracing::{info, warn};
+use url::Url;
+use zkboost_client::{Error, ProofOutcome, zkBoostClient};
+use zkboost_types::{
+    Decode, FailureReason, Hash256, MainnetEthSpec, NewPayloadRequest, ProofType,
+    RecordedProofRequest,
+};
+
+/// Arguments of the `replay` subcommand.
+#[derive(Debug, Args)]
+pub(crate) struct ReplayArgs {
+    /// Directory with requests recorded by a server configured with `record_dir`.
+    #[arg(long)]
+    dir: PathBuf,
+    /// Endpoint of the server running the reference version.
+    #[arg(long)]
+    baseline: Url,
+    /// Endpoint of the server running the version under test.
+    #[arg(long)]
+    candidate: Url,
+    /// Seconds to wait for all proofs of a single request.
+    #[arg(long, default_value_t = 300)]
+    timeout_secs: u64,
+    /// Only replay requests of blocks at or above this number.
+    #[arg(long)]
+    from: Option<u64>,
+    /// Only replay requests of blocks at or below this number.
+    #[arg(long)]
+    to: Option<u64>,
+    /// Send requests at their recorded pace, sped up by this factor (e.g. `2` for twice as fast).
+    /// Requests are sent back-to-back if unset. A request is never sent before the previous one
+    /// finished.
+    #[arg(long)]
+    speed: Option<f64>,
+}
+
+/// Outcome of a proof request on a single server.
+#[derive(Debug)]
+enum Outcome {
+    Proof(Bytes),
+    Failure(FailureReason),
+    Missing,
+}
+
+impl Outcome {
+    fn describe(&self) -> String {
+        match self {
+            Self::Proof(proof) => format!("proof ({} bytes)", proof.len()),
+            Self::Failure(reason) => format!("failure ({reason:?})"),
+            Self::Missing => "no outcome".to_string(),
+        }
+    }
+}
+
+pub(crate) async fn run(args: ReplayArgs) -> anyhow::Result<()> {
+    ensure!(
+        args.speed.is_none_or(|speed| speed > 0.0),
+        "--speed must be > 0"
+    );
+    let records = load_records(&args.dir)?
+        .into_iter()
+        .filter(|(_, new_payload_request)| {
+            let block_number = new_payload_request.block_number();
+            args.from.is_none_or(|from| block_number >= from)
+                && args.to.is_none_or(|to| block_number <= to)
+        })
+        .collect::<Vec<_>>();
+    info!(count = records.len(), dir = %args.dir.display(), "loaded recorded requests");
+
+    let baseline = zkBoostClient::new(args.baseline);
+    let candidate = zkBoostClient::new(args.candidate);
+    let proof_timeout = Duration::from_secs(args.timeout_secs);
+
+    let start = Instant::now();
+    let first_received_at_ms = records
+        .first()
+        .map_or(0, |(record, _)| record.received_at_ms);
+    let mut diffs = 0;
+    for (record, new_payload_request) in &records {
+        if let Some(speed) = args.speed {
+            let offset_ms = record.received_at_ms - first_received_at_ms;
+            sleep_until(start + Duration::from_millis(offset_ms).div_f64(speed)).await;
+        }
+        let root = record.new_payload_request_root;
+        info!(%root, proof_types = ?record.proof_types, "replaying");
+
+        let (baseline_outcomes, candidate_outcomes) = tokio::join!(
+            prove(
+                &baseline,
+                new_payload_request,
+                &record.proof_types,
+                proof_timeout
+            ),
+            prove(
+                &candidate,
+                new_payload_request,
+                &record.proof_types,
+                proof_timeout
+            ),
+        );
+        let mut baseline_outcomes = baseline_outcomes.context("baseline server")?;
+        let mut candidate_outcomes = candidate_outcomes.context("candidate server")?;
+
+        for &proof_type in &record.proof_types {
+            let baseline_outcome = baseline_outcomes
+                .remove(&proof_type)
+                .unwrap_or(Outcome::Missing);
+            let candidate_outcome = candidate_outcomes
+                .remove(&proof_type)
+                .unwrap_or(Outcome::Missing);
+            match compare(
+                &baseline,
+                &candidate,
+                root,
+                proof_type,
+                &baseline_outcome,
+                &candidate_outcome,
+            )
+            .await?
+            {
+                None => println!("{root} {proof_type} ok"),
+                Some(diff) => {
+                    diffs += 1;
+                    println!("{root} {proof_type} DIFF {diff}");
+                }
+            }
+        }
+    }
+
+    println!("replayed {} requests, {diffs} differences", records.len());
+    if diffs > 0 {
+        bail!("{diffs} differences found");
+    }
+    Ok(())
+}
+
+/// Loads all recorded requests in `dir`, ordered by the time they were received.
+fn load_records(
+    dir: &Path,
+) -> anyhow::Result<Vec<(RecordedProofRequest, NewPayloadRequest<MainnetEthSpec>)>> {
+    let mut records = Vec::new();
+    for entry in fs::read_dir(dir).with_context(|| format!("read {}", dir.display()))? {
+        let path = entry?.path();
+        if path.extension().is_none_or(|extension| extension != "json") {
+            continue;
+        }
+        let record: RecordedProofRequest = serde_json::from_slice(&fs::read(&path)?)
+            .with_context(|| format!("parse {}", path.display()))?;
+        let body_path = path.with_extension("ssz");
+        let body = fs::read(&body_path).with_context(|| format!("read {}", body_path.display()))?;
+        let new_payload_request = NewPayloadRequest::from_ssz_bytes(&body)
+            .map_err(|e| anyhow!("decode {}: {e:?}", body_path.display()))?;
+        records.push((record, new_payload_request));
+    }
+    records.sort_by_key(|(record, _)| record.received_at_ms);
+    Ok(records)
+}
+
+/// Requests proofs on a server and waits until every proof type completed, failed, or the timeout
+/// elapsed.
+async fn prove(
+    client: &zkBoostClient,
+    new_payload_request: &NewPayloadRequest<MainnetEthSpec>,
+    proof_types: &[ProofType],
+    proof_timeout: Duration,
+) -> Result<HashMap<ProofType, Outcome>, Error> {
+    let outcomes = client
+        .prove_block(new_payload_request, proof_types, proof_timeout)
+        .await?;
+    if outcomes.len() < proof_types.len() {
+        warn!("timed out waiting for proofs");
+    }
+    Ok(outcomes
+        .into_iter()
+        .map(|(proof_type, outcome)| {
+            let outcome = match outcome {
+                ProofOutcome::Proof(proof) => Outcome::Proof(proof),
+                ProofOutcome::Failure(proof_failure) => Outcome::Failure(proof_failure.reason),
+            };
+            (proof_type, outcome)
+        })
+        .collect())
+}
+
+/// Compares the outcomes of both servers, returning a description of the difference if any.
+///
+/// When both servers produced a proof, each proof is verified by both servers so that diverging
+/// public values or verifier behaviour show up as a difference.
+async fn compare(
+    baseline: &zkBoostClient,
+    candidate: &zkBoostClient,
+    root: Hash256,
+    proof_type: ProofType,
+    baseline_outcome: &Outcome,
+    candidate_outcome: &Outcome,
+) -> anyhow::Result<Option<String>> {
+    match (baseline_outcome, candidate_outcome) {
+        (Outcome::Proof(baseline_proof), Outcome::Proof(candidate_proof)) => {
+            let mut rejected = Vec::new();
+            for (verifier_name, verifier) in [("baseline", baseline), ("candidate", candidate)] {
+                for (prover_name, proof) in
+                    [("baseline", baseline_proof), ("candidate", candidate_proof)]
+                {
+                    let status = verifier.verify_proof(root, proof_type, proof).await?.status;
+                    if !status.is_valid() {
+                        rejected.push(format!("{prover_name} proof rejected by {verifier_name}"));
+                    }
+                }
+            }
+            Ok((!rejected.is_empty()).then(|| rejected.join(", ")))
+        }
+        (Outcome::Failure(baseline_reason), Outcome::Failure(candidate_reason))
+            if baseline_reason == candidate_reason =>
+        {
+            Ok(None)
+        }
+        (Outcome::Missing, Outcome::Missing) => Ok(None),
+        (baseline_outcome, candidate_outcome) => Ok(Some(format!(
+            "baseline {} vs candidate {}",
+            baseline_outcome.describe(),
+            candidate_outcome.describe()
+        ))),
+    }
+}
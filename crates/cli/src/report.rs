@@ -0,0 +1,129 @@
+//! `zkboost-cli report`: summarizes proof results files written by servers configured with
+//! `results_path`, comparing every run against the first one.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, ensure};
+use clap::Args;
+use zkboost_types::{ProofResultRecord, ProofType};
+
+/// Arguments of the `report` subcommand.
+#[derive(Debug, Args)]
+pub(crate) struct ReportArgs {
+    /// Results files to summarize, the first one is the baseline the others are compared to.
+    #[arg(required = true)]
+    files: Vec<PathBuf>,
+}
+
+/// Aggregated results of a single proof type within one run.
+#[derive(Debug, Default)]
+struct Summary {
+    total: usize,
+    proving_times_ms: Vec<u64>,
+    proof_sizes: Vec<u64>,
+    mgas: f64,
+}
+
+impl Summary {
+    fn add(&mut self, record: &ProofResultRecord) {
+        self.total += 1;
+        if record.failure_reason.is_none() {
+            self.proving_times_ms.push(record.proving_time_ms);
+            self.proof_sizes.push(record.proof_size);
+            self.mgas += record.gas_used as f64 / 1e6;
+        }
+    }
+
+    fn successes(&self) -> usize {
+        self.proving_times_ms.len()
+    }
+
+    /// Returns the `q` quantile of successful proving times in milliseconds.
+    fn proving_time_quantile_ms(&self, q: f64) -> Option<u64> {
+        let mut sorted = self.proving_times_ms.clone();
+        sorted.sort_unstable();
+        let index = ((sorted.len() as f64 - 1.0) * q).round() as usize;
+        sorted.get(index).copied()
+    }
+
+    fn mean_proof_size(&self) -> Option<f64> {
+        (!self.proof_sizes.is_empty())
+            .then(|| self.proof_sizes.iter().sum::<u64>() as f64 / self.proof_sizes.len() as f64)
+    }
+
+    fn ms_per_mgas(&self) -> Option<f64> {
+        (self.mgas > 0.0).then(|| self.proving_times_ms.iter().sum::<u64>() as f64 / self.mgas)
+    }
+}
+
+pub(crate) fn run(args: ReportArgs) -> anyhow::Result<()> {
+    let runs = args
+        .files
+        .iter()
+        .map(|path| load_summaries(path))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    let proof_types: BTreeSet<ProofType> = runs
+        .iter()
+        .flat_map(|summaries| summaries.keys().copied())
+        .collect();
+
+    println!(
+        "{:<16} {:<32} {:>9} {:>10} {:>10} {:>10} {:>12} {:>10}",
+        "proof_type", "run", "success", "p50 ms", "p90 ms", "ms/Mgas", "proof bytes", "p50 diff"
+    );
+    for proof_type in proof_types {
+        let baseline_p50 = runs[0]
+            .get(&proof_type)
+            .and_then(|summary| summary.proving_time_quantile_ms(0.5));
+        for (path, summaries) in args.files.iter().zip(&runs) {
+            let Some(summary) = summaries.get(&proof_type) else {
+                continue;
+            };
+            let p50 = summary.proving_time_quantile_ms(0.5);
+            let diff = match (baseline_p50, p50) {
+                (Some(baseline), Some(p50)) if baseline > 0 => format!(
+                    "{:+.1}%",
+                    (p50 as f64 - baseline as f64) / baseline as f64 * 100.0
+                ),
+                _ => "-".to_string(),
+            };
+            println!(
+                "{:<16} {:<32} {:>9} {:>10} {:>10} {:>10} {:>12} {:>10}",
+                proof_type.as_str(),
+                path.display().to_string(),
+                format!("{}/{}", summary.successes(), summary.total),
+                display(p50),
+                display(summary.proving_time_quantile_ms(0.9)),
+                display(summary.ms_per_mgas().map(|v| v.round() as u64)),
+                display(summary.mean_proof_size().map(|v| v.round() as u64)),
+                diff,
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Loads a results file and aggregates its records per proof type.
+fn load_summaries(path: &Path) -> anyhow::Result<BTreeMap<ProofType, Summary>> {
+    let content = fs::read_to_string(path).with_context(|| format!("read {}", path.display()))?;
+    let mut summaries = BTreeMap::<ProofType, Summary>::new();
+    for (index, line) in content.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let record: ProofResultRecord = serde_json::from_str(line)
+            .with_context(|| format!("parse {}:{}", path.display(), index + 1))?;
+        summaries.entry(record.proof_type).or_default().add(&record);
+    }
+    ensure!(!summaries.is_empty(), "no results in {}", path.display());
+    Ok(summaries)
+}
+
+fn display(value: Option<u64>) -> String {
+    value.map_or_else(|| "-".to_string(), |value| value.to_string())
+}
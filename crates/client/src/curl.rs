@@ -0,0 +1,118 @@
+//! Rendering of requests as equivalent `curl` commands, to reproduce requests against remote
+//! servers in bug reports.
+
+use std::{
+    fmt::Write,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use sha2::{Digest, Sha256};
+use zkboost_types::Hash256;
+
+/// Renders `request` as a `curl` command.
+///
+/// Bodies are written to `body_dir` as `{sha256}.body` and passed with `--data-binary @file`, so
+/// binary SSZ payloads survive copy and paste. Streamed bodies cannot be rendered, so the command
+/// reads them from stdin and ends with a comment saying so.
+pub fn render(request: &reqwest::Request, body_dir: &Path) -> io::Result<String> {
+    let mut command = format!("curl -X {}", request.method());
+    for (name, value) in request.headers() {
+        let value = String::from_utf8_lossy(value.as_bytes());
+        write!(command, " -H {}", quote(&format!("{name}: {value}"))).unwrap();
+    }
+    let mut streamed = false;
+    match request.body().map(|body| body.as_bytes()) {
+        Some(Some(body)) => {
+            let path = write_body(body, body_dir)?;
+            write!(
+                command,
+                " --data-binary {}",
+                quote(&format!("@{}", path.display()))
+            )
+            .unwrap();
+        }
+        Some(None) => {
+            command.push_str(" --data-binary @-");
+            streamed = true;
+        }
+        None => {}
+    }
+    write!(command, " {}", quote(request.url().as_str())).unwrap();
+    if streamed {
+        command.push_str(" # streamed body not rendered");
+    }
+    Ok(command)
+}
+
+fn write_body(body: &[u8], body_dir: &Path) -> io::Result<PathBuf> {
+    fs::create_dir_all(body_dir)?;
+    let digest = Hash256::from_slice(&Sha256::digest(body));
+    let path = body_dir.join(format!("{digest}.body"));
+    fs::write(&path, body)?;
+    Ok(path)
+}
+
+/// Quotes `value` for POSIX shells.
+fn quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use reqwest::header::CONTENT_TYPE;
+
+    use super::render;
+
+    #[test]
+    fn test_render() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let body_dir = tempdir.path().join("curl");
+        let request = reqwest::Client::new()
+            .post("http://localhost:3000/v1/execution_proof_requests?proof_types=reth-sp1")
+            .header(CONTENT_TYPE, "application/octet-stream")
+            .header("x-note", "it's")
+            .body(vec![0, 1, 2])
+            .build()
+            .unwrap();
+
+        let command = render(&request, &body_dir).unwrap();
+
+        let body_path = body_dir
+            .join("0xae4b3280e56e2faf83f414a6e3dabe9d5fbe18976544c05fed121accb85b53fc.body");
+        assert_eq!(
+            command,
+            format!(
+                "curl -X POST -H 'content-type: application/octet-stream' -H 'x-note: it'\\''s' \
+                 --data-binary '@{}' \
+                 'http://localhost:3000/v1/execution_proof_requests?proof_types=reth-sp1'",
+                body_path.display()
+            )
+        );
+        assert_eq!(fs::read(&body_path).unwrap(), [0, 1, 2]);
+    }
+
+    #[test]
+    fn test_render_streamed_body() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let body_dir = tempdir.path().join("curl");
+        let chunks = futures::stream::iter([Ok::<_, std::io::Error>(vec![0, 1, 2])]);
+        let request = reqwest::Client::new()
+            .post("http://localhost:3000/v1/execution_proof_requests?proof_types=reth-sp1")
+            .body(reqwest::Body::wrap_stream(chunks))
+            .build()
+            .unwrap();
+
+        let command = render(&request, &body_dir).unwrap();
+
+        assert_eq!(
+            command,
+            "curl -X POST --data-binary @- \
+             'http://localhost:3000/v1/execution_proof_requests?proof_types=reth-sp1' \
+             # streamed body not rendered"
+        );
+        assert!(!body_dir.exists());
+    }
+}
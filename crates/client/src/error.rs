@@ -41,6 +41,19 @@ pub enum Error {
     #[error("SSE event parse error: {0}")]
     EventParse(#[from] zkboost_types::ProofEventParseError),
 
+    /// A downloaded proof does not match the digest announced by the server.
+    #[error("proof checksum mismatch: expected {expected}, got {actual}")]
+    ChecksumMismatch {
+        /// Digest announced by the server.
+        expected: String,
+        /// Digest of the downloaded bytes.
+        actual: String,
+    },
+
+    /// Failed to record a request in debug mode.
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
     /// Failed to construct a URL.
     #[error("URL error: {0}")]
     Url(#[from] url::ParseError),
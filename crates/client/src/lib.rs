@@ -3,10 +3,26 @@
 //! Provides [`zkBoostClient`] with methods for all four API operations:
 //!
 //! - [`request_proof`](zkBoostClient::request_proof) - submit a `NewPayloadRequest` for proving
+//!   ([`request_proof_stream`](zkBoostClient::request_proof_stream) to upload it from an
+//!   [`AsyncRead`](tokio::io::AsyncRead), with
+//!   [`request_proof_stream_with_progress`](zkBoostClient::request_proof_stream_with_progress) to
+//!   report upload progress)
 //! - [`subscribe_proof_events`](zkBoostClient::subscribe_proof_events) - stream SSE proof
 //!   completion/failure events
 //! - [`get_proof`](zkBoostClient::get_proof) - download completed proof bytes
+//!   ([`get_proof_resumable`](zkBoostClient::get_proof_resumable) to resume interrupted downloads,
+//!   [`get_proof_with_progress`](zkBoostClient::get_proof_with_progress) to report download
+//!   progress)
 //! - [`verify_proof`](zkBoostClient::verify_proof) - verify a proof against the server
+//! - [`get_queue`](zkBoostClient::get_queue) - inspect queued and running proofs
+//! - [`get_proving_time_estimate`](zkBoostClient::get_proving_time_estimate) - p50/p95 proving time
+//!   of recent proofs
+//!
+//! [`prove_block`](zkBoostClient::prove_block) combines the above into a single call that requests
+//! proofs for a block and waits for their outcomes, [`prove_on_all`] does the same on several
+//! servers concurrently. [`ProofSpool`] keeps downloaded proofs on disk until they are forwarded.
+//! [`with_curl_debug`](zkBoostClient::with_curl_debug) records every request as a `curl` command
+//! for bug reports.
 //!
 //! # Example
 //!
@@ -24,35 +40,111 @@
 
 #![warn(unused_crate_dependencies)]
 
+pub mod curl;
 pub mod error;
+pub mod spool;
+
+use std::{collections::HashMap, fs, io::Write, path::PathBuf, time::Duration};
 
 use bytes::Bytes;
-use futures::stream::Stream;
-use reqwest::{Response, StatusCode, header::CONTENT_TYPE};
+use futures::stream::{FuturesUnordered, Stream};
+use reqwest::{
+    RequestBuilder, Response, StatusCode,
+    header::{CONTENT_TYPE, RANGE},
+};
 use reqwest_eventsource::{Event, EventSource};
 use serde::de::DeserializeOwned;
+use sha2::{Digest, Sha256};
+use tokio::io::AsyncRead;
 use tokio_stream::StreamExt;
+use tokio_util::io::ReaderStream;
 use url::Url;
+use zkboost_types::TreeHash;
 
 #[rustfmt::skip]
 pub use {
     error::Error,
+    spool::ProofSpool,
     zkboost_types::{
-        Encode, FailureReason, Hash256, MainnetEthSpec,
-        NewPayloadRequest, ProofComplete, ProofEvent, ProofFailure, ProofRequestResponse,
-        ProofStatus, ProofType, ProofVerificationResponse,
-        ProofEventParseError,
+        BackendKind, Encode, FailureReason, HardwareInfo, Hash256, InvalidProofReason,
+        MainnetEthSpec, NewPayloadRequest, PROOF_SHA256_HEADER, ProofComplete, ProofEvent,
+        ProofEventParseError, ProofFailure, ProofRequestResponse, ProofStatus, ProofType,
+        ProofTypeInfo, ProofTypeQueue, ProofTypesResponse, ProofVerificationResponse,
+        ProvingTimeEstimate, QueueEstimate, QueueResponse, QueuedProof, REQUEST_ID_HEADER,
+        ServerInfoResponse, SignedServerInfo,
     },
 };
 
 const APPLICATION_OCTET_STREAM: &str = "application/octet-stream";
 
+/// Outcome of a single proof type returned by [`zkBoostClient::prove_block`].
+#[derive(Debug, Clone)]
+pub enum ProofOutcome {
+    /// The proof was generated, holding the proof bytes.
+    Proof(Bytes),
+    /// Proof generation failed.
+    Failure(ProofFailure),
+}
+
+/// Progress of an upload or download, reported after every transferred chunk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransferProgress {
+    /// Bytes transferred so far.
+    pub transferred: u64,
+    /// Total bytes to transfer, `None` if unknown.
+    pub total: Option<u64>,
+}
+
+/// Connection settings of the HTTP client built by [`zkBoostClient::with_http_options`], reqwest
+/// defaults where unset.
+#[derive(Debug, Clone, Default)]
+pub struct HttpOptions {
+    /// Speak HTTP/2 over cleartext without negotiation, multiplexing concurrent requests over a
+    /// single connection. The server must accept h2c, which zkboost-server does.
+    pub http2_prior_knowledge: bool,
+    /// Interval of HTTP/2 PING frames keeping connections alive while idle.
+    pub http2_keep_alive_interval: Option<Duration>,
+    /// Interval of TCP keep-alive probes.
+    pub tcp_keepalive: Option<Duration>,
+    /// How long idle connections are kept in the pool.
+    pub pool_idle_timeout: Option<Duration>,
+    /// Maximum number of idle connections kept per host.
+    pub pool_max_idle_per_host: Option<usize>,
+    /// `User-Agent` of requests, e.g. to tell callers apart in the server's logs.
+    pub user_agent: Option<String>,
+}
+
+impl HttpOptions {
+    fn build_client(&self) -> reqwest::Result<reqwest::Client> {
+        let mut builder = reqwest::Client::builder()
+            .http2_keep_alive_interval(self.http2_keep_alive_interval)
+            .tcp_keepalive(self.tcp_keepalive);
+        if self.http2_prior_knowledge {
+            builder = builder.http2_prior_knowledge();
+        }
+        if self.http2_keep_alive_interval.is_some() {
+            builder = builder.http2_keep_alive_while_idle(true);
+        }
+        if let Some(pool_idle_timeout) = self.pool_idle_timeout {
+            builder = builder.pool_idle_timeout(pool_idle_timeout);
+        }
+        if let Some(pool_max_idle_per_host) = self.pool_max_idle_per_host {
+            builder = builder.pool_max_idle_per_host(pool_max_idle_per_host);
+        }
+        if let Some(user_agent) = &self.user_agent {
+            builder = builder.user_agent(user_agent);
+        }
+        builder.build()
+    }
+}
+
 /// HTTP client for the zkboost Proof Node API.
 #[derive(Debug, Clone)]
 #[allow(non_camel_case_types)]
 pub struct zkBoostClient {
     endpoint: Url,
     http_client: reqwest::Client,
+    curl_dir: Option<PathBuf>,
 }
 
 impl zkBoostClient {
@@ -61,6 +153,7 @@ impl zkBoostClient {
         Self {
             endpoint,
             http_client: reqwest::Client::new(),
+            curl_dir: None,
         }
     }
 
@@ -69,9 +162,29 @@ impl zkBoostClient {
         Self {
             endpoint,
             http_client,
+            curl_dir: None,
         }
     }
 
+    /// Creates a new client with an HTTP client built from `options`, e.g. to use HTTP/2 for many
+    /// concurrent uploads.
+    pub fn with_http_options(endpoint: Url, options: &HttpOptions) -> Result<Self, Error> {
+        Ok(Self::with_http_client(endpoint, options.build_client()?))
+    }
+
+    /// Enables debug mode: every request is appended to `dir/requests.sh` as an equivalent `curl`
+    /// command before it is sent, with bodies in files next to it, see [`curl::render`]. The SSE
+    /// stream of [`subscribe_proof_events`](Self::subscribe_proof_events) is not recorded.
+    pub fn with_curl_debug(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.curl_dir = Some(dir.into());
+        self
+    }
+
+    /// Returns the base URL of the server.
+    pub fn endpoint(&self) -> &Url {
+        &self.endpoint
+    }
+
     /// Submit a [`NewPayloadRequest`] for proof generation.
     ///
     /// Sends `POST /v1/execution_proof_requests?proof_types=...` with the SSZ-encoded body. Returns
@@ -81,22 +194,68 @@ impl zkBoostClient {
         new_payload_request: &NewPayloadRequest<MainnetEthSpec>,
         proof_types: &[ProofType],
     ) -> Result<ProofRequestResponse, Error> {
-        let mut url = self.endpoint.join("/v1/execution_proof_requests")?;
-        let proof_types = Vec::from_iter(proof_types.iter().map(ProofType::as_str)).join(",");
-        url.query_pairs_mut()
-            .append_pair("proof_types", &proof_types);
+        let url = self.proof_request_url(proof_types)?;
+
+        let request = self
+            .http_client
+            .post(url)
+            .header(CONTENT_TYPE, APPLICATION_OCTET_STREAM)
+            .body(new_payload_request.as_ssz_bytes());
+        let response = self.send(request).await?;
+
+        handle_json_response(response).await
+    }
+
+    /// Submit an SSZ-encoded `NewPayloadRequest` read from `reader` for proof generation.
+    ///
+    /// Same as [`request_proof`](Self::request_proof), but streams the body instead of encoding it
+    /// in memory, e.g. to pipe a large request from disk.
+    pub async fn request_proof_stream(
+        &self,
+        reader: impl AsyncRead + Send + Sync + 'static,
+        proof_types: &[ProofType],
+    ) -> Result<ProofRequestResponse, Error> {
+        self.request_proof_stream_with_progress(reader, None, proof_types, |_| {})
+            .await
+    }
+
+    /// Same as [`request_proof_stream`](Self::request_proof_stream), calling `on_progress` after
+    /// every uploaded chunk. `total` is the size of the request if known, reported as is.
+    pub async fn request_proof_stream_with_progress(
+        &self,
+        reader: impl AsyncRead + Send + Sync + 'static,
+        total: Option<u64>,
+        proof_types: &[ProofType],
+        mut on_progress: impl FnMut(TransferProgress) + Send + Sync + 'static,
+    ) -> Result<ProofRequestResponse, Error> {
+        let url = self.proof_request_url(proof_types)?;
 
-        let response = self
+        let mut transferred = 0;
+        let body = ReaderStream::new(reader).map(move |chunk| {
+            if let Ok(chunk) = &chunk {
+                transferred += chunk.len() as u64;
+                on_progress(TransferProgress { transferred, total });
+            }
+            chunk
+        });
+        let request = self
             .http_client
             .post(url)
             .header(CONTENT_TYPE, APPLICATION_OCTET_STREAM)
-            .body(new_payload_request.as_ssz_bytes())
-            .send()
-            .await?;
+            .body(reqwest::Body::wrap_stream(body));
+        let response = self.send(request).await?;
 
         handle_json_response(response).await
     }
 
+    fn proof_request_url(&self, proof_types: &[ProofType]) -> Result<Url, Error> {
+        let mut url = self.endpoint.join("/v1/execution_proof_requests")?;
+        let proof_types = Vec::from_iter(proof_types.iter().map(ProofType::as_str)).join(",");
+        url.query_pairs_mut()
+            .append_pair("proof_types", &proof_types);
+        Ok(url)
+    }
+
     /// Subscribe to SSE proof events.
     ///
     /// Opens `GET /v1/execution_proof_requests` as an SSE stream.
@@ -108,28 +267,38 @@ impl zkBoostClient {
         filter_root: Option<Hash256>,
     ) -> impl Stream<Item = Result<ProofEvent, Error>> + Send + '_ {
         async_stream::try_stream! {
-            let mut url = self.endpoint.join("/v1/execution_proof_requests")?;
-            if let Some(new_payload_request_root) = filter_root {
-                url.query_pairs_mut()
-                    .append_pair("new_payload_request_root", &new_payload_request_root.to_string());
+            let es = self.open_proof_events(filter_root).await?;
+            for await proof_event in proof_events(es) {
+                yield proof_event?;
             }
+        }
+    }
 
-            let builder = self.http_client.get(url);
-            let mut es = EventSource::new(builder)
-                .map_err(|e| Error::Sse(format!("failed to create event source: {e}")))?;
+    /// Opens the SSE stream of proof events and waits until the server accepted it, so no event
+    /// sent afterwards is missed.
+    async fn open_proof_events(&self, filter_root: Option<Hash256>) -> Result<EventSource, Error> {
+        let mut url = self.endpoint.join("/v1/execution_proof_requests")?;
+        if let Some(new_payload_request_root) = filter_root {
+            url.query_pairs_mut().append_pair(
+                "new_payload_request_root",
+                &new_payload_request_root.to_string(),
+            );
+        }
 
-            while let Some(event) = es.next().await {
-                match event {
-                    Ok(Event::Open) => {}
-                    Ok(Event::Message(message)) => {
-                        yield ProofEvent::try_from_parts(&message.event, &message.data)?;
-                    }
-                    Err(error) => {
-                        es.close();
-                        Err(Error::Sse(error.to_string()))?;
-                    }
-                }
+        let builder = self.http_client.get(url);
+        let mut es = EventSource::new(builder)
+            .map_err(|e| Error::Sse(format!("failed to create event source: {e}")))?;
+        match es.next().await {
+            Some(Ok(Event::Open)) => Ok(es),
+            Some(Ok(Event::Message(message))) => Err(Error::Sse(format!(
+                "unexpected event {} before the stream opened",
+                message.event
+            ))),
+            Some(Err(error)) => {
+                es.close();
+                Err(Error::Sse(error.to_string()))
             }
+            None => Err(Error::Sse("event stream closed".to_string())),
         }
     }
 
@@ -146,10 +315,115 @@ impl zkBoostClient {
             "/v1/execution_proofs/{new_payload_request_root}/{proof_type}"
         ))?;
 
-        let response = error_for_status(self.http_client.get(url).send().await?).await?;
+        let response = error_for_status(self.send(self.http_client.get(url)).await?).await?;
         Ok(response.bytes().await?)
     }
 
+    /// Download an execution proof, letting the server wait up to `wait` for it to complete.
+    ///
+    /// Sends `GET /v1/execution_proofs/{root}/{proof_type}?wait_secs=...`, a simpler alternative
+    /// to following [`subscribe_proof_events`](Self::subscribe_proof_events) for batch tooling.
+    /// Returns [`Error::NotFound`] if the proof failed or did not complete in time. The server
+    /// caps the wait one second below its request timeout, so a longer `wait` can end early with
+    /// [`Error::NotFound`] and should be retried.
+    pub async fn wait_for_proof(
+        &self,
+        new_payload_request_root: Hash256,
+        proof_type: ProofType,
+        wait: Duration,
+    ) -> Result<Bytes, Error> {
+        let mut url = self.endpoint.join(&format!(
+            "/v1/execution_proofs/{new_payload_request_root}/{proof_type}"
+        ))?;
+        url.query_pairs_mut()
+            .append_pair("wait_secs", &wait.as_secs().to_string());
+
+        let response = error_for_status(self.send(self.http_client.get(url)).await?).await?;
+        Ok(response.bytes().await?)
+    }
+
+    /// Same as [`get_proof`](Self::get_proof), calling `on_progress` after every downloaded
+    /// chunk. The total is unknown for compressed downloads.
+    pub async fn get_proof_with_progress(
+        &self,
+        new_payload_request_root: Hash256,
+        proof_type: ProofType,
+        mut on_progress: impl FnMut(TransferProgress),
+    ) -> Result<Bytes, Error> {
+        let url = self.endpoint.join(&format!(
+            "/v1/execution_proofs/{new_payload_request_root}/{proof_type}"
+        ))?;
+
+        let mut response = error_for_status(self.send(self.http_client.get(url)).await?).await?;
+        let total = response.content_length();
+        let mut proof = Vec::new();
+        while let Some(chunk) = response.chunk().await.map_err(Error::Transport)? {
+            proof.extend_from_slice(&chunk);
+            on_progress(TransferProgress {
+                transferred: proof.len() as u64,
+                total,
+            });
+        }
+        Ok(Bytes::from(proof))
+    }
+
+    /// Download a completed execution proof, resuming with `Range` requests after interrupted
+    /// transfers.
+    ///
+    /// Up to `max_attempts` requests are made in total. The assembled proof is checked against
+    /// the SHA-256 digest the server announces in the `x-proof-sha256` header.
+    pub async fn get_proof_resumable(
+        &self,
+        new_payload_request_root: Hash256,
+        proof_type: ProofType,
+        max_attempts: usize,
+    ) -> Result<Bytes, Error> {
+        let url = self.endpoint.join(&format!(
+            "/v1/execution_proofs/{new_payload_request_root}/{proof_type}"
+        ))?;
+
+        let mut proof = Vec::new();
+        let mut expected_sha256 = None;
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+            let mut request = self.http_client.get(url.clone());
+            if !proof.is_empty() {
+                request = request.header(RANGE, format!("bytes={}-", proof.len()));
+            }
+            let result = async {
+                let mut response = error_for_status(self.send(request).await?).await?;
+                if !proof.is_empty() && response.status() != StatusCode::PARTIAL_CONTENT {
+                    // Server ignored the range, start over.
+                    proof.clear();
+                }
+                expected_sha256 = response
+                    .headers()
+                    .get(PROOF_SHA256_HEADER)
+                    .and_then(|value| value.to_str().ok())
+                    .map(str::to_string);
+                while let Some(chunk) = response.chunk().await.map_err(Error::Transport)? {
+                    proof.extend_from_slice(&chunk);
+                }
+                Ok::<_, Error>(())
+            }
+            .await;
+            match result {
+                Ok(()) => break,
+                Err(Error::Request(_) | Error::Transport(_)) if attempt < max_attempts => continue,
+                Err(error) => return Err(error),
+            }
+        }
+
+        if let Some(expected) = expected_sha256 {
+            let actual = Hash256::from_slice(&Sha256::digest(&proof)).to_string();
+            if actual != expected {
+                return Err(Error::ChecksumMismatch { expected, actual });
+            }
+        }
+        Ok(Bytes::from(proof))
+    }
+
     /// Verify a proof against the server.
     ///
     /// Sends `POST /v1/execution_proof_verifications?new_payload_request_root=...&proof_type=...`
@@ -168,16 +442,196 @@ impl zkBoostClient {
             )
             .append_pair("proof_type", proof_type.as_str());
 
-        let response = self
+        let request = self
             .http_client
             .post(url)
             .header(CONTENT_TYPE, APPLICATION_OCTET_STREAM)
-            .body(proof.to_vec())
-            .send()
-            .await?;
+            .body(proof.to_vec());
+        let response = self.send(request).await?;
+
+        handle_json_response(response).await
+    }
+
+    /// Request proofs for a block and wait until every proof type completed or failed.
+    ///
+    /// Subscribes to the SSE stream of the block before requesting the proofs, then downloads
+    /// proofs already cached by the server and awaits the others on the stream, downloading them
+    /// on completion. Proof types without an outcome when `timeout` elapses are absent from the
+    /// returned map. Duplicates in `proof_types` are requested once.
+    ///
+    /// The client does not check the proofs or their public values: public values can only be
+    /// read from a proof by the zkVM's verifier. Use [`verify_proof`](Self::verify_proof) to have
+    /// the server verify a proof and check that its public values commit to a successful
+    /// validation of `new_payload_request`.
+    pub async fn prove_block(
+        &self,
+        new_payload_request: &NewPayloadRequest<MainnetEthSpec>,
+        proof_types: &[ProofType],
+        timeout: Duration,
+    ) -> Result<HashMap<ProofType, ProofOutcome>, Error> {
+        let mut requested = Vec::with_capacity(proof_types.len());
+        for &proof_type in proof_types {
+            if !requested.contains(&proof_type) {
+                requested.push(proof_type);
+            }
+        }
+
+        let root = new_payload_request.tree_hash_root();
+        let mut proof_events = Box::pin(proof_events(self.open_proof_events(Some(root)).await?));
+        self.request_proof(new_payload_request, &requested).await?;
+
+        let mut outcomes = HashMap::new();
+        for &proof_type in &requested {
+            match self.get_proof(root, proof_type).await {
+                Ok(proof) => {
+                    outcomes.insert(proof_type, ProofOutcome::Proof(proof));
+                }
+                Err(Error::NotFound(_)) => {}
+                Err(error) => return Err(error),
+            }
+        }
+
+        let wait = async {
+            while outcomes.len() < requested.len() {
+                let Some(proof_event) = proof_events.next().await else {
+                    break;
+                };
+                let proof_event = proof_event?;
+                let proof_type = proof_event.proof_type();
+                if !requested.contains(&proof_type) || outcomes.contains_key(&proof_type) {
+                    continue;
+                }
+                match proof_event {
+                    ProofEvent::ProofComplete(proof_complete) => {
+                        let proof = self.get_proof(root, proof_complete.proof_type).await?;
+                        outcomes.insert(proof_complete.proof_type, ProofOutcome::Proof(proof));
+                    }
+                    ProofEvent::ProofFailure(proof_failure) => {
+                        outcomes.insert(
+                            proof_failure.proof_type,
+                            ProofOutcome::Failure(proof_failure),
+                        );
+                    }
+                }
+            }
+            Ok::<_, Error>(())
+        };
+        if let Ok(result) = tokio::time::timeout(timeout, wait).await {
+            result?;
+        }
 
+        Ok(outcomes)
+    }
+
+    /// Get the configured proof types and whether their backends can prove and verify.
+    ///
+    /// Sends `GET /v1/proof_types`.
+    pub async fn get_proof_types(&self) -> Result<ProofTypesResponse, Error> {
+        let url = self.endpoint.join("/v1/proof_types")?;
+        let response = self.send(self.http_client.get(url)).await?;
+        handle_json_response(response).await
+    }
+
+    /// Get the server info signed by the server operator, including `nonce` so the signature is
+    /// known to be fresh.
+    ///
+    /// Sends `GET /v1/info/signed?nonce=...`. The signature is not checked, verify it against the
+    /// operator's minisign public key before trusting [`SignedServerInfo::decode_info`].
+    pub async fn get_signed_info(&self, nonce: &str) -> Result<SignedServerInfo, Error> {
+        let mut url = self.endpoint.join("/v1/info/signed")?;
+        url.query_pairs_mut().append_pair("nonce", nonce);
+        let response = self.send(self.http_client.get(url)).await?;
         handle_json_response(response).await
     }
+
+    /// Get the queued and running proofs of every proving backend.
+    ///
+    /// Sends `GET /v1/queue`.
+    pub async fn get_queue(&self) -> Result<QueueResponse, Error> {
+        let url = self.endpoint.join("/v1/queue")?;
+        let response = self.send(self.http_client.get(url)).await?;
+        handle_json_response(response).await
+    }
+
+    /// Sends `request`, first recording it as a `curl` command in debug mode.
+    async fn send(&self, request: RequestBuilder) -> Result<Response, Error> {
+        let Some(curl_dir) = &self.curl_dir else {
+            return Ok(request.send().await?);
+        };
+        let request = request.build()?;
+        let command = curl::render(&request, curl_dir)?;
+        fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(curl_dir.join("requests.sh"))
+            .and_then(|mut file| writeln!(file, "{command}"))?;
+        Ok(self.http_client.execute(request).await?)
+    }
+
+    /// Get p50/p95 proving time estimates of a proof type from recent proofs, of blocks with
+    /// similar `gas_used` if given.
+    ///
+    /// Sends `GET /v1/proof_types/{proof_type}/estimate?gas_used=...`.
+    pub async fn get_proving_time_estimate(
+        &self,
+        proof_type: ProofType,
+        gas_used: Option<u64>,
+    ) -> Result<ProvingTimeEstimate, Error> {
+        let mut url = self
+            .endpoint
+            .join(&format!("/v1/proof_types/{proof_type}/estimate"))?;
+        if let Some(gas_used) = gas_used {
+            url.query_pairs_mut()
+                .append_pair("gas_used", &gas_used.to_string());
+        }
+        let response = self.send(self.http_client.get(url)).await?;
+        handle_json_response(response).await
+    }
+}
+
+/// Runs [`zkBoostClient::prove_block`] on every client concurrently, yielding each client with its
+/// outcomes as soon as it finished, fastest first.
+///
+/// Useful to race several servers for time-critical proofs: the caller can stop polling the stream
+/// once a satisfying result arrived, which drops the remaining in-flight waits.
+pub fn prove_on_all<'a>(
+    clients: &'a [zkBoostClient],
+    new_payload_request: &'a NewPayloadRequest<MainnetEthSpec>,
+    proof_types: &'a [ProofType],
+    timeout: Duration,
+) -> impl Stream<
+    Item = (
+        &'a zkBoostClient,
+        Result<HashMap<ProofType, ProofOutcome>, Error>,
+    ),
+> + 'a {
+    clients
+        .iter()
+        .map(|client| async move {
+            let outcomes = client
+                .prove_block(new_payload_request, proof_types, timeout)
+                .await;
+            (client, outcomes)
+        })
+        .collect::<FuturesUnordered<_>>()
+}
+
+/// Yields the proof events of an opened SSE stream, ending it on the first error.
+fn proof_events(mut es: EventSource) -> impl Stream<Item = Result<ProofEvent, Error>> + Send {
+    async_stream::try_stream! {
+        while let Some(event) = es.next().await {
+            match event {
+                Ok(Event::Open) => {}
+                Ok(Event::Message(message)) => {
+                    yield ProofEvent::try_from_parts(&message.event, &message.data)?;
+                }
+                Err(error) => {
+                    es.close();
+                    Err(Error::Sse(error.to_string()))?;
+                }
+            }
+        }
+    }
 }
 
 async fn error_for_status(response: Response) -> Result<Response, Error> {
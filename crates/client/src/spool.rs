@@ -0,0 +1,174 @@
+//! On-disk spool of downloaded proofs.
+//!
+//! Consumers that forward proofs elsewhere can [`store`](ProofSpool::store) every proof right after
+//! downloading it and [`remove`](ProofSpool::remove) it once forwarded. After a crash,
+//! [`pending`](ProofSpool::pending) lists the proofs that were received but not forwarded yet.
+
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
+
+use bytes::Bytes;
+use tokio::fs;
+use zkboost_types::{Hash256, ProofType};
+
+const EXTENSION: &str = "proof";
+
+/// Directory of proofs, one `{new_payload_request_root}-{proof_type}.proof` file per proof.
+#[derive(Debug, Clone)]
+pub struct ProofSpool {
+    dir: PathBuf,
+}
+
+impl ProofSpool {
+    /// Opens the spool in `dir`, creating the directory if it does not exist.
+    pub async fn open(dir: impl Into<PathBuf>) -> io::Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir).await?;
+        Ok(Self { dir })
+    }
+
+    /// Returns the spool directory.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Stores a proof, replacing a previously stored one.
+    ///
+    /// The proof is written to a temporary file first and renamed into place, so a crash never
+    /// leaves a truncated proof behind. On Unix the directory is synced after the rename, so a
+    /// stored proof also survives a power loss.
+    pub async fn store(
+        &self,
+        new_payload_request_root: Hash256,
+        proof_type: ProofType,
+        proof: &[u8],
+    ) -> io::Result<()> {
+        let path = self.path(new_payload_request_root, proof_type);
+        let tmp_path = path.with_extension("tmp");
+        fs::write(&tmp_path, proof).await?;
+        fs::File::open(&tmp_path).await?.sync_all().await?;
+        fs::rename(&tmp_path, &path).await?;
+        #[cfg(unix)]
+        fs::File::open(&self.dir).await?.sync_all().await?;
+        Ok(())
+    }
+
+    /// Loads a stored proof, `None` if it is not in the spool.
+    pub async fn load(
+        &self,
+        new_payload_request_root: Hash256,
+        proof_type: ProofType,
+    ) -> io::Result<Option<Bytes>> {
+        match fs::read(self.path(new_payload_request_root, proof_type)).await {
+            Ok(proof) => Ok(Some(proof.into())),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Removes a stored proof, e.g. once it has been forwarded.
+    pub async fn remove(
+        &self,
+        new_payload_request_root: Hash256,
+        proof_type: ProofType,
+    ) -> io::Result<()> {
+        match fs::remove_file(self.path(new_payload_request_root, proof_type)).await {
+            Err(error) if error.kind() != io::ErrorKind::NotFound => Err(error),
+            _ => Ok(()),
+        }
+    }
+
+    /// Lists the stored proofs. Files not written by the spool are skipped.
+    pub async fn pending(&self) -> io::Result<Vec<(Hash256, ProofType)>> {
+        let mut pending = Vec::new();
+        let mut entries = fs::read_dir(&self.dir).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            if path
+                .extension()
+                .is_none_or(|extension| extension != EXTENSION)
+            {
+                continue;
+            }
+            let Some((root, proof_type)) = path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .and_then(|stem| stem.split_once('-'))
+            else {
+                continue;
+            };
+            if let (Ok(root), Ok(proof_type)) = (root.parse(), proof_type.parse()) {
+                pending.push((root, proof_type));
+            }
+        }
+        Ok(pending)
+    }
+
+    fn path(&self, new_payload_request_root: Hash256, proof_type: ProofType) -> PathBuf {
+        self.dir.join(format!(
+            "{new_payload_request_root}-{proof_type}.{EXTENSION}"
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::fs;
+    use zkboost_types::{Hash256, ProofType};
+
+    use super::ProofSpool;
+
+    #[tokio::test]
+    async fn test_spool_round_trip() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let dir = tempdir.path().join("spool");
+        let spool = ProofSpool::open(&dir).await.unwrap();
+        let root = Hash256::repeat_byte(1);
+
+        spool
+            .store(root, ProofType::RethSP1, &[1, 2])
+            .await
+            .unwrap();
+        spool
+            .store(root, ProofType::EthrexZisk, &[3])
+            .await
+            .unwrap();
+        spool
+            .store(root, ProofType::EthrexZisk, &[4])
+            .await
+            .unwrap();
+        // Files not written by the spool are skipped.
+        fs::write(dir.join("notes.txt"), "").await.unwrap();
+        fs::write(dir.join("garbage.proof"), "").await.unwrap();
+
+        // Reopening finds the proofs stored before, as after a restart.
+        let spool = ProofSpool::open(&dir).await.unwrap();
+        let mut pending = spool.pending().await.unwrap();
+        pending.sort();
+        assert_eq!(
+            pending,
+            [(root, ProofType::EthrexZisk), (root, ProofType::RethSP1)]
+        );
+        assert_eq!(
+            spool
+                .load(root, ProofType::EthrexZisk)
+                .await
+                .unwrap()
+                .unwrap(),
+            [4].as_slice()
+        );
+
+        spool.remove(root, ProofType::EthrexZisk).await.unwrap();
+        spool.remove(root, ProofType::EthrexZisk).await.unwrap();
+        assert_eq!(spool.pending().await.unwrap(), [(root, ProofType::RethSP1)]);
+        assert!(
+            spool
+                .load(root, ProofType::EthrexZisk)
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+}
@@ -1,9 +1,79 @@
 //! Guest program loader, loading and verifying guest program ELF and signature.
-use std::path::PathBuf;
+use std::{
+    fmt::Write,
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
 
 use anyhow::{Context, Result, anyhow};
 use minisign::{PublicKey, SignatureBox};
 use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Provenance of a loaded program, recorded alongside the verified program.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Provenance {
+    /// URL or path the program was fetched from.
+    pub program: String,
+    /// URL or path the signature was fetched from, `None` if the program was loaded unsigned.
+    pub signature: Option<String>,
+    /// Hex-encoded SHA-256 of the program.
+    pub sha256: String,
+    /// Minisign key ID of the publisher key, `None` if the program was loaded unsigned.
+    pub key_id: Option<String>,
+    /// Unix timestamp (seconds) the program was fetched at.
+    pub fetched_at: u64,
+}
+
+impl Provenance {
+    /// Records the provenance of `program_bytes` fetched now, with `signature` and
+    /// `publisher_public_key` set if the signature was verified.
+    pub fn new(
+        program: &str,
+        program_bytes: &[u8],
+        signature: Option<(&str, &str)>,
+    ) -> Result<Self> {
+        let (signature, key_id) = match signature {
+            Some((signature, publisher_public_key)) => (
+                Some(signature.to_string()),
+                Some(public_key_id(publisher_public_key)?),
+            ),
+            None => (None, None),
+        };
+        Ok(Self {
+            program: program.to_string(),
+            signature,
+            sha256: Sha256::digest(program_bytes)
+                .iter()
+                .fold(String::new(), |mut hex, byte| {
+                    let _ = write!(hex, "{byte:02x}");
+                    hex
+                }),
+            key_id,
+            fetched_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |elapsed| elapsed.as_secs()),
+        })
+    }
+}
+
+/// Returns the minisign key ID (uppercase hex, as printed by `minisign`) of a public key.
+pub fn public_key_id(publisher_public_key: &str) -> Result<String> {
+    let public_key = PublicKey::from_base64(publisher_public_key)
+        .map_err(|_| anyhow!("Invalid base64 public key"))?;
+    let public_key_box = public_key
+        .to_box()
+        .map_err(|_| anyhow!("Failed to encode public key"))?
+        .to_string();
+    // The untrusted comment reads `untrusted comment: minisign public key <KEY_ID>`.
+    public_key_box
+        .lines()
+        .next()
+        .and_then(|comment| comment.rsplit(' ').next())
+        .map(str::to_string)
+        .ok_or_else(|| anyhow!("Failed to read public key ID"))
+}
 
 /// Trait for HTTP client
 pub trait HttpClient {
@@ -121,7 +191,10 @@ mod tests {
     use anyhow::{Result, anyhow};
     use minisign::KeyPair;
 
-    use crate::{HttpClient, load_and_verify_with_url, verify_program_and_signature};
+    use crate::{
+        HttpClient, Provenance, load_and_verify_with_url, public_key_id,
+        verify_program_and_signature,
+    };
 
     struct MockHttpClient {
         bytes_responses: std::collections::HashMap<String, Vec<u8>>,
@@ -172,6 +245,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_provenance() {
+        let keypair = KeyPair::generate_unencrypted_keypair().unwrap();
+        let pk_str = keypair.pk.to_base64();
+        let key_id = public_key_id(&pk_str).unwrap();
+        assert!(!key_id.is_empty());
+        assert!(key_id.chars().all(|c| c.is_ascii_hexdigit()));
+
+        let provenance = Provenance::new(
+            "http://example.com/program.elf",
+            b"abc",
+            Some(("http://example.com/program.elf.minisig", &pk_str)),
+        )
+        .unwrap();
+        assert_eq!(
+            provenance.sha256,
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(provenance.key_id, Some(key_id));
+
+        let unsigned = Provenance::new("program.elf", b"abc", None).unwrap();
+        assert_eq!(unsigned.signature, None);
+        assert_eq!(unsigned.key_id, None);
+    }
+
     #[tokio::test]
     async fn test_load_and_verify_with_url() {
         let keypair = KeyPair::generate_unencrypted_keypair().unwrap();
@@ -2,34 +2,76 @@
 //!
 //! This tool fetches a program ELF and its signature from URLs or local paths,
 //! verifies the signature against a given public key, and saves the verified
-//! program to an output file.
+//! program to an output file, together with a `<output>.provenance.json`
+//! recording where it was fetched from, its digest and the signing key ID.
+//!
+//! With `--manifest`, every program listed in a JSON manifest is verified and
+//! saved into `--output-dir`, and a zkboost-server `[[zkvm]]` config fragment
+//! is printed for the entries that name a proof type.
 
-use std::path::PathBuf;
+use std::{fmt::Write, path::PathBuf};
 
-use anyhow::{Context, Result};
+use anyhow::{Context, Result, bail, ensure};
 use clap::Parser;
-use guest_loader::verify_program_and_signature;
-use reqwest::Client;
+use guest_loader::{Provenance, verify_program_and_signature};
+use reqwest::{Client, StatusCode};
+use serde::Deserialize;
 use tokio::fs;
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Args {
     /// URL or path to the program ELF
-    #[arg(long, short = 'p')]
-    program: String,
+    #[arg(long, short = 'p', required_unless_present = "manifest")]
+    program: Option<String>,
 
-    /// URL or path to the signature
-    #[arg(long, short = 's')]
-    signature: String,
+    /// URL or path to the signature, defaults to the `.minisig` sidecar of the program
+    #[arg(long, short = 's', conflicts_with = "manifest")]
+    signature: Option<String>,
+
+    /// Load the program unverified instead of failing when the signature does not exist
+    #[arg(long)]
+    allow_missing_signature: bool,
 
     /// URL, path, or direct string for the public key
     #[arg(long, short = 'k')]
     public_key: String,
 
     /// Output path for the verified program
-    #[arg(long, short = 'o')]
-    output: PathBuf,
+    #[arg(long, short = 'o', required_unless_present = "manifest")]
+    output: Option<PathBuf>,
+
+    /// Path to a JSON manifest listing several programs to verify
+    #[arg(long, short = 'm', conflicts_with_all = ["program", "output"], requires = "output_dir")]
+    manifest: Option<PathBuf>,
+
+    /// Output directory for the programs verified from the manifest
+    #[arg(long)]
+    output_dir: Option<PathBuf>,
+}
+
+/// Manifest of programs to verify in one run.
+#[derive(Debug, Deserialize)]
+struct Manifest {
+    programs: Vec<ManifestEntry>,
+}
+
+/// A program listed in a [`Manifest`].
+#[derive(Debug, Deserialize)]
+struct ManifestEntry {
+    /// File name of the verified program in the output directory.
+    name: String,
+    /// URL or path to the program.
+    program: String,
+    /// URL or path to the signature, defaults to the `.minisig` sidecar of the program.
+    #[serde(default)]
+    signature: Option<String>,
+    /// Expected hex-encoded SHA-256 of the program.
+    #[serde(default)]
+    sha256: Option<String>,
+    /// zkboost proof type (e.g. `reth-zisk`) to emit a verifier config entry for.
+    #[serde(default)]
+    proof_type: Option<String>,
 }
 
 #[tokio::main]
@@ -37,30 +79,134 @@ async fn main() -> Result<()> {
     let args = Args::parse();
     let client = Client::new();
 
-    println!("Fetching program from: {}", args.program);
-    let program_bytes = fetch_artifact_bytes(&args.program, &client).await?;
+    let Some(manifest_path) = &args.manifest else {
+        let (program, output) = args
+            .program
+            .as_deref()
+            .zip(args.output.as_ref())
+            .expect("required unless --manifest is given");
+        let (program_bytes, provenance) =
+            load_program(&args, program, args.signature.clone(), &client).await?;
+        return write_program(output.clone(), &program_bytes, &provenance).await;
+    };
 
-    println!("Fetching signature from: {}", args.signature);
-    let signature_str = fetch_artifact_string(&args.signature, &client).await?;
-    let signature_str = signature_str.trim().to_string();
+    let manifest: Manifest = serde_json::from_slice(
+        &fs::read(manifest_path)
+            .await
+            .with_context(|| format!("Failed to read manifest: {manifest_path:?}"))?,
+    )
+    .with_context(|| format!("Failed to parse manifest: {manifest_path:?}"))?;
+    let output_dir = args.output_dir.as_ref().expect("required with --manifest");
 
-    println!("Resolving public key...");
-    let public_key_str = resolve_public_key(&args.public_key, &client).await?;
-    let public_key_str = public_key_str.trim().to_string();
+    let mut config_fragment = String::new();
+    for entry in &manifest.programs {
+        let (program_bytes, provenance) =
+            load_program(&args, &entry.program, entry.signature.clone(), &client)
+                .await
+                .with_context(|| format!("Failed to load {}", entry.name))?;
+        if let Some(expected) = &entry.sha256 {
+            ensure!(
+                provenance.sha256.eq_ignore_ascii_case(expected),
+                "Digest mismatch for {}: expected {expected}, got {}",
+                entry.name,
+                provenance.sha256
+            );
+        }
+        let output = output_dir.join(&entry.name);
+        write_program(output.clone(), &program_bytes, &provenance).await?;
 
-    println!("Verifying program and signature...");
-    verify_program_and_signature(&program_bytes, &signature_str, &public_key_str)
-        .context("Verification failed")?;
+        if let Some(proof_type) = &entry.proof_type {
+            let output = fs::canonicalize(&output).await?;
+            let _ = writeln!(config_fragment, "[[zkvm]]");
+            let _ = writeln!(config_fragment, "kind = \"verifier\"");
+            let _ = writeln!(config_fragment, "proof_type = {proof_type:?}");
+            let _ = writeln!(
+                config_fragment,
+                "program_vk_url = {:?}",
+                output.display().to_string()
+            );
+            if let Some(signature) = &provenance.signature {
+                let public_key = resolve_public_key(&args.public_key, &client).await?;
+                let _ = writeln!(
+                    config_fragment,
+                    "publisher_public_key = {:?}",
+                    public_key.trim()
+                );
+                let _ = writeln!(config_fragment, "program_vk_signature_url = {signature:?}");
+            }
+            config_fragment.push('\n');
+        }
+    }
+    println!("Verified {} programs", manifest.programs.len());
 
-    println!("Verification successful!");
+    if !config_fragment.is_empty() {
+        println!("\nzkboost-server config:\n\n{}", config_fragment.trim_end());
+    }
 
-    if let Some(parent) = args.output.parent()
+    Ok(())
+}
+
+/// Fetches a program and its signature and verifies it, returning the program with its
+/// provenance.
+async fn load_program(
+    args: &Args,
+    program: &str,
+    signature: Option<String>,
+    client: &Client,
+) -> Result<(Vec<u8>, Provenance)> {
+    println!("Fetching program from: {program}");
+    let program_bytes = fetch_artifact_bytes(program, client).await?;
+
+    let signature = signature.unwrap_or_else(|| format!("{program}.minisig"));
+    println!("Fetching signature from: {signature}");
+    let provenance = match fetch_optional_artifact_string(&signature, client).await? {
+        Some(signature_str) => {
+            let signature_str = signature_str.trim().to_string();
+
+            println!("Resolving public key...");
+            let public_key_str = resolve_public_key(&args.public_key, client).await?;
+            let public_key_str = public_key_str.trim().to_string();
+
+            println!("Verifying program and signature...");
+            verify_program_and_signature(&program_bytes, &signature_str, &public_key_str)
+                .context("Verification failed")?;
+
+            println!("Verification successful!");
+            Provenance::new(program, &program_bytes, Some((&signature, &public_key_str)))?
+        }
+        None if args.allow_missing_signature => {
+            println!("WARNING: signature not found, program is NOT verified");
+            Provenance::new(program, &program_bytes, None)?
+        }
+        None => bail!(
+            "Signature not found at {signature}, pass --allow-missing-signature to load the program unverified"
+        ),
+    };
+    println!("Program sha256: {}", provenance.sha256);
+    if let Some(key_id) = &provenance.key_id {
+        println!("Signed by key ID: {key_id}");
+    }
+    Ok((program_bytes, provenance))
+}
+
+/// Writes a verified program to `output` and its provenance next to it.
+async fn write_program(
+    output: PathBuf,
+    program_bytes: &[u8],
+    provenance: &Provenance,
+) -> Result<()> {
+    if let Some(parent) = output.parent()
         && !parent.as_os_str().is_empty()
     {
         fs::create_dir_all(parent).await?;
     }
-    fs::write(&args.output, &program_bytes).await?;
-    println!("Verified program written to: {:?}", args.output);
+    fs::write(&output, program_bytes).await?;
+    println!("Program written to: {output:?}");
+
+    let mut provenance_path = output.into_os_string();
+    provenance_path.push(".provenance.json");
+    fs::write(&provenance_path, serde_json::to_vec_pretty(provenance)?).await?;
+    println!("Provenance written to: {provenance_path:?}");
 
     Ok(())
 }
@@ -93,6 +239,21 @@ async fn fetch_artifact_string(source: &str, client: &Client) -> Result<String>
     }
 }
 
+/// Like [`fetch_artifact_string`], but returns `None` if the artifact does not exist.
+async fn fetch_optional_artifact_string(source: &str, client: &Client) -> Result<Option<String>> {
+    if source.starts_with("http://") || source.starts_with("https://") {
+        let response = client.get(source).send().await?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        Ok(Some(response.error_for_status()?.text().await?))
+    } else if PathBuf::from(source).exists() {
+        fetch_artifact_string(source, client).await.map(Some)
+    } else {
+        Ok(None)
+    }
+}
+
 async fn resolve_public_key(source: &str, client: &Client) -> Result<String> {
     if source.starts_with("http://") || source.starts_with("https://") {
         return fetch_artifact_string(source, client).await;
@@ -42,5 +42,103 @@ fn test_cli_verify_and_save() -> Result<()> {
     let verified_data = fs::read(&output_path)?;
     assert_eq!(verified_data, program_data);
 
+    let provenance: serde_json::Value = serde_json::from_slice(&fs::read(
+        temp_dir.path().join("verified.elf.provenance.json"),
+    )?)?;
+    assert_eq!(provenance["program"], program_path.to_str().unwrap());
+    assert!(provenance["key_id"].is_string());
+
+    Ok(())
+}
+
+#[test]
+fn test_cli_missing_signature() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let program_path = temp_dir.path().join("program.elf");
+    let output_path = temp_dir.path().join("verified.elf");
+
+    let keypair = KeyPair::generate_unencrypted_keypair().unwrap();
+    fs::write(&program_path, b"unsigned program")?;
+
+    let mut cmd = Command::new(env!("CARGO_BIN_EXE_guest-loader"));
+    cmd.arg("--program")
+        .arg(program_path.to_str().unwrap())
+        .arg("--public-key")
+        .arg(keypair.pk.to_base64())
+        .arg("--output")
+        .arg(output_path.to_str().unwrap());
+    cmd.assert().failure();
+    assert!(!output_path.exists());
+
+    cmd.arg("--allow-missing-signature");
+    cmd.assert().success();
+
+    let provenance: serde_json::Value = serde_json::from_slice(&fs::read(
+        temp_dir.path().join("verified.elf.provenance.json"),
+    )?)?;
+    assert!(provenance["signature"].is_null());
+    assert!(provenance["key_id"].is_null());
+
+    Ok(())
+}
+
+#[test]
+fn test_cli_manifest() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let keypair = KeyPair::generate_unencrypted_keypair().unwrap();
+    let program_data = b"manifest program data".to_vec();
+    let program_path = temp_dir.path().join("program.bin");
+    let signature_box = minisign::sign(
+        None,
+        &keypair.sk,
+        Cursor::new(program_data.clone()),
+        None,
+        None,
+    )
+    .unwrap();
+    fs::write(&program_path, &program_data)?;
+    fs::write(
+        temp_dir.path().join("program.bin.minisig"),
+        signature_box.to_string(),
+    )?;
+
+    let sha256 = guest_loader::Provenance::new("", &program_data, None)?.sha256;
+    let manifest_path = temp_dir.path().join("manifest.json");
+    let output_dir = temp_dir.path().join("programs");
+    let write_manifest = |sha256: &str| {
+        fs::write(
+            &manifest_path,
+            serde_json::json!({
+                "programs": [{
+                    "name": "reth-zisk.vk",
+                    "program": program_path.to_str().unwrap(),
+                    "sha256": sha256,
+                    "proof_type": "reth-zisk",
+                }]
+            })
+            .to_string(),
+        )
+    };
+
+    let mut cmd = Command::new(env!("CARGO_BIN_EXE_guest-loader"));
+    cmd.arg("--manifest")
+        .arg(manifest_path.to_str().unwrap())
+        .arg("--output-dir")
+        .arg(output_dir.to_str().unwrap())
+        .arg("--public-key")
+        .arg(keypair.pk.to_base64());
+
+    write_manifest(&"00".repeat(32))?;
+    cmd.assert().failure();
+
+    write_manifest(&sha256)?;
+    let output = cmd.assert().success().get_output().stdout.clone();
+    let output = String::from_utf8(output)?;
+    assert!(output.contains("proof_type = \"reth-zisk\""));
+    assert!(output.contains("publisher_public_key"));
+
+    assert_eq!(fs::read(output_dir.join("reth-zisk.vk"))?, program_data);
+    assert!(output_dir.join("reth-zisk.vk.provenance.json").exists());
+
     Ok(())
 }
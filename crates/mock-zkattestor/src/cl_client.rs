@@ -28,11 +28,10 @@ pub(crate) struct ClClient {
 }
 
 impl ClClient {
-    pub(crate) fn new(base_url: Url) -> Self {
-        Self {
-            base_url,
-            http: reqwest::Client::new(),
-        }
+    /// Basic auth credentials in the userinfo of `base_url` are sent by reqwest as is, proxy
+    /// and default headers (e.g. a bearer token) are taken from `http`.
+    pub(crate) fn new(base_url: Url, http: reqwest::Client) -> Self {
+        Self { base_url, http }
     }
 
     pub(crate) fn subscribe_block_events(
@@ -2,13 +2,18 @@
 
 #![warn(unused_crate_dependencies)]
 
-use std::{collections::HashSet, sync::Arc};
+use std::{
+    collections::HashSet,
+    sync::{Arc, Mutex, RwLock},
+    time::Duration,
+};
 
 use anyhow::bail;
 use cl_client::{ClClient, new_payload_request_from_beacon_block};
 use clap::Parser;
 use futures::StreamExt;
 use lighthouse_types::Hash256;
+use reqwest::header::{AUTHORIZATION, HeaderMap, HeaderName, HeaderValue};
 use tracing::{info, warn};
 use tracing_subscriber::EnvFilter;
 use url::Url;
@@ -17,14 +22,32 @@ use zkboost_types::{ProofEvent, ProofType};
 
 mod cl_client;
 
+/// Backoff before the first retry of the initial proof types refresh, doubled on every retry.
+const REFRESH_RETRY_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Upper bound of the backoff between retries of the initial proof types refresh.
+const MAX_REFRESH_RETRY_BACKOFF: Duration = Duration::from_secs(30);
+
 #[derive(Parser)]
 struct Cli {
     #[arg(long)]
     cl_endpoint: Url,
+    /// Bearer token for a CL endpoint behind an authenticating proxy.
+    #[arg(long)]
+    cl_bearer_token: Option<String>,
+    /// HTTP(S) proxy for CL requests, `HTTP_PROXY`/`HTTPS_PROXY` are honored otherwise.
+    #[arg(long)]
+    http_proxy: Option<Url>,
+    /// Extra `name: value` header sent with every CL request, can be repeated.
+    #[arg(long = "header", value_parser = parse_header)]
+    headers: Vec<(HeaderName, HeaderValue)>,
     #[arg(long)]
     zkboost_endpoint: Url,
     #[arg(long, value_delimiter = ',')]
     proof_types: Vec<ProofType>,
+    /// Interval in seconds to re-check which proof types zkboost can prove.
+    #[arg(long, default_value_t = 60)]
+    capability_refresh_secs: u64,
 }
 
 #[tokio::main]
@@ -36,9 +59,33 @@ async fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
 
     let mock_attestor = Arc::new(MockAttestor {
-        cl_client: ClClient::new(cli.cl_endpoint),
-        zkboost_client: zkBoostClient::new(cli.zkboost_endpoint),
-        proof_types: cli.proof_types,
+        cl_client: ClClient::new(cli.cl_endpoint.clone(), cl_http_client(&cli)?),
+        zkboost_client: zkBoostClient::new(cli.zkboost_endpoint.clone()),
+        proof_types: RwLock::new(Vec::new()),
+        unsupported: Mutex::new(Vec::new()),
+        in_flight: Mutex::new(HashSet::new()),
+    });
+
+    // Blocks are only requested once the provable proof types are known.
+    let mut backoff = REFRESH_RETRY_BACKOFF;
+    while let Err(error) = mock_attestor.refresh_proof_types(&cli.proof_types).await {
+        warn!(%error, ?backoff, "proof types refresh failed, retrying");
+        tokio::time::sleep(backoff).await;
+        backoff = (backoff * 2).min(MAX_REFRESH_RETRY_BACKOFF);
+    }
+
+    tokio::spawn({
+        let mock_attestor = mock_attestor.clone();
+        let configured = cli.proof_types.clone();
+        let interval = Duration::from_secs(cli.capability_refresh_secs);
+        async move {
+            loop {
+                tokio::time::sleep(interval).await;
+                if let Err(error) = mock_attestor.refresh_proof_types(&configured).await {
+                    warn!(%error, "proof types refresh failed");
+                }
+            }
+        }
     });
 
     let mut stream = Box::pin(mock_attestor.cl_client.subscribe_block_events());
@@ -54,21 +101,93 @@ async fn main() -> anyhow::Result<()> {
     bail!("block stream ended")
 }
 
+fn parse_header(header: &str) -> anyhow::Result<(HeaderName, HeaderValue)> {
+    let Some((name, value)) = header.split_once(':') else {
+        bail!("expected `name: value`, got `{header}`");
+    };
+    Ok((name.trim().parse()?, value.trim().parse()?))
+}
+
+fn cl_http_client(cli: &Cli) -> anyhow::Result<reqwest::Client> {
+    let mut headers = cli.headers.iter().cloned().collect::<HeaderMap>();
+    if let Some(token) = &cli.cl_bearer_token {
+        let mut value = HeaderValue::from_str(&format!("Bearer {token}"))?;
+        value.set_sensitive(true);
+        headers.insert(AUTHORIZATION, value);
+    }
+    let mut builder = reqwest::Client::builder().default_headers(headers);
+    if let Some(proxy) = &cli.http_proxy {
+        builder = builder.proxy(reqwest::Proxy::all(proxy.as_str())?);
+    }
+    Ok(builder.build()?)
+}
+
 struct MockAttestor {
     cl_client: ClClient,
     zkboost_client: zkBoostClient,
-    proof_types: Vec<ProofType>,
+    /// Configured proof types zkboost can currently prove.
+    proof_types: RwLock<Vec<ProofType>>,
+    /// Configured proof types zkboost could not prove at the last refresh, to warn only about
+    /// newly unsupported ones.
+    unsupported: Mutex<Vec<ProofType>>,
+    /// Roots of the blocks currently being proved, so a block reported twice (e.g. after the
+    /// event stream reconnects) is only requested once.
+    in_flight: Mutex<HashSet<Hash256>>,
 }
 
 impl MockAttestor {
+    /// Keeps the configured proof types zkboost has a proving backend for, warning about the ones
+    /// that lost theirs.
+    async fn refresh_proof_types(&self, configured: &[ProofType]) -> anyhow::Result<()> {
+        let response = self.zkboost_client.get_proof_types().await?;
+        let provable = response
+            .proof_types
+            .iter()
+            .filter(|info| info.can_prove)
+            .map(|info| info.proof_type)
+            .collect::<HashSet<_>>();
+        let (supported, unsupported): (Vec<_>, Vec<_>) = configured
+            .iter()
+            .copied()
+            .partition(|proof_type| provable.contains(proof_type));
+        let mut previously_unsupported = self.unsupported.lock().unwrap();
+        for proof_type in &unsupported {
+            if !previously_unsupported.contains(proof_type) {
+                warn!(%proof_type, "zkboost has no proving backend for proof type, skipping it");
+            }
+        }
+        *previously_unsupported = unsupported;
+        let mut proof_types = self.proof_types.write().unwrap();
+        if *proof_types != supported {
+            info!(?supported, "proof types updated");
+            *proof_types = supported;
+        }
+        Ok(())
+    }
+
     async fn process_block(&self, block_root: Hash256) -> anyhow::Result<()> {
+        if !self.in_flight.lock().unwrap().insert(block_root) {
+            info!(block = %block_root, "block already in flight, skipping");
+            return Ok(());
+        }
+        let result = self.prove_block(block_root).await;
+        self.in_flight.lock().unwrap().remove(&block_root);
+        result
+    }
+
+    async fn prove_block(&self, block_root: Hash256) -> anyhow::Result<()> {
+        let proof_types = self.proof_types.read().unwrap().clone();
+        if proof_types.is_empty() {
+            bail!("no provable proof types");
+        }
+
         let beacon_block = self.cl_client.get_beacon_block(block_root).await?;
         let new_payload_request = new_payload_request_from_beacon_block(&beacon_block)?;
 
         let block_hash = new_payload_request.block_hash();
         let resp = self
             .zkboost_client
-            .request_proof(&new_payload_request, &self.proof_types)
+            .request_proof(&new_payload_request, &proof_types)
             .await?;
         let new_payload_request_root = resp.new_payload_request_root;
         info!(%new_payload_request_root, %block_hash, "proof requested");
@@ -77,7 +196,7 @@ impl MockAttestor {
             self.zkboost_client
                 .subscribe_proof_events(Some(new_payload_request_root)),
         );
-        let mut remaining: HashSet<ProofType> = self.proof_types.iter().copied().collect();
+        let mut remaining: HashSet<ProofType> = proof_types.iter().copied().collect();
 
         while !remaining.is_empty() {
             let Some(Ok(proof_event)) = proof_events.next().await else {
@@ -0,0 +1,84 @@
+//! Exposes build metadata to the server as compile-time environment variables.
+
+use std::{
+    env, fs,
+    path::Path,
+    process::Command,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+fn main() {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let lock_path = manifest_dir.join("../../Cargo.lock");
+    rerun_if_changed(&lock_path);
+    // `HEAD` changes on checkouts, the branch ref on commits to the checked out branch, and
+    // `packed-refs` when git packs the branch ref.
+    let branch = git(manifest_dir, &["symbolic-ref", "-q", "HEAD"]);
+    for git_path in ["HEAD", "packed-refs"].into_iter().chain(branch.as_deref()) {
+        if let Some(path) = git(manifest_dir, &["rev-parse", "--git-path", git_path]) {
+            rerun_if_changed(&manifest_dir.join(path));
+        }
+    }
+    println!("cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH");
+
+    let lock = fs::read_to_string(&lock_path).ok();
+    let ere_version = lock
+        .as_deref()
+        .and_then(|lock| package_field(lock, "ere-server-client", "version"))
+        .unwrap_or_else(|| "unknown".to_string());
+    println!("cargo:rustc-env=ZKBOOST_ERE_VERSION={ere_version}");
+
+    // e.g. `git+https://github.com/eth-act/ere-guests?tag=v0.10.0#2d519d4f...`
+    let ere_guests_source = lock
+        .as_deref()
+        .and_then(|lock| package_field(lock, "stateless-validator-common", "source"))
+        .unwrap_or_else(|| "unknown".to_string());
+    println!("cargo:rustc-env=ZKBOOST_ERE_GUESTS_SOURCE={ere_guests_source}");
+
+    let git_sha =
+        git(manifest_dir, &["rev-parse", "HEAD"]).unwrap_or_else(|| "unknown".to_string());
+    println!("cargo:rustc-env=ZKBOOST_GIT_SHA={git_sha}");
+
+    // Honor `SOURCE_DATE_EPOCH` so reproducible builds produce identical binaries.
+    let build_timestamp = env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|epoch| epoch.parse::<u64>().ok())
+        .unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |elapsed| elapsed.as_secs())
+        });
+    println!("cargo:rustc-env=ZKBOOST_BUILD_TIMESTAMP={build_timestamp}");
+}
+
+/// Returns the trimmed output of a successful git command run in `dir`.
+fn git(dir: &Path, args: &[&str]) -> Option<String> {
+    Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|output| output.trim().to_string())
+}
+
+/// Reruns the build script when `path` changes. Missing paths are skipped, cargo would rerun the
+/// build script on every build otherwise.
+fn rerun_if_changed(path: &Path) {
+    if path.exists() {
+        println!("cargo:rerun-if-changed={}", path.display());
+    }
+}
+
+/// Returns `field` of the first package named `name` in a `Cargo.lock`.
+fn package_field(lock: &str, name: &str, field: &str) -> Option<String> {
+    let name_line = format!("name = \"{name}\"");
+    let field_prefix = format!("{field} = \"");
+    lock.lines()
+        .skip_while(|line| *line != name_line)
+        .skip(1)
+        .take_while(|line| !line.is_empty() && !line.starts_with("name = "))
+        .find_map(|line| line.strip_prefix(&field_prefix)?.strip_suffix('"'))
+        .map(str::to_string)
+}
@@ -5,13 +5,15 @@
 
 use std::path::PathBuf;
 
-use clap::Parser;
+use anyhow::Context;
+use clap::{Parser, ValueEnum};
 use tokio::signal::unix::{SignalKind, signal};
 use tokio_util::sync::CancellationToken;
 use tracing::{error, info};
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
 use tracing_subscriber::{EnvFilter, layer::SubscriberExt, util::SubscriberInitExt};
 use zkboost_server::{
-    config::Config,
+    config::{Config, EXAMPLE_CONFIG},
     metrics::{init_metrics, spawn_upkeep},
     server::zkBoostServer,
 };
@@ -19,29 +21,111 @@ use zkboost_server::{
 #[derive(Parser)]
 struct Cli {
     /// Path to configuration file.
-    #[arg(long, short)]
-    config: PathBuf,
+    #[arg(long, short, required_unless_present = "print_example_config")]
+    config: Option<PathBuf>,
+    /// Validate the configuration file and exit.
+    #[arg(long)]
+    check_config: bool,
+    /// Print an annotated example configuration showing all options and exit.
+    #[arg(long, exclusive = true)]
+    print_example_config: bool,
+    /// Reject proof requests, overriding `read_only` of the configuration file.
+    #[arg(long)]
+    read_only: bool,
+    /// Log line format.
+    #[arg(long, value_enum, default_value_t = LogFormat::Text)]
+    log_format: LogFormat,
+    /// Write logs to rotated `zkboost.*.log` files in this directory instead of stdout.
+    #[arg(long)]
+    log_dir: Option<PathBuf>,
+    /// How often the log file in `--log-dir` is rotated.
+    #[arg(long, value_enum, default_value_t = LogRotation::Daily)]
+    log_rotation: LogRotation,
+    /// Number of rotated log files kept in `--log-dir`, all if unset.
+    #[arg(long)]
+    log_max_files: Option<usize>,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum LogFormat {
+    /// Human-readable compact lines.
+    Text,
+    /// One JSON object per line, including span fields.
+    Json,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum LogRotation {
+    /// A new file every hour.
+    Hourly,
+    /// A new file every day.
+    Daily,
+    /// A single file.
+    Never,
 }
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+
+    if cli.print_example_config {
+        print!("{EXAMPLE_CONFIG}");
+        return Ok(());
+    }
+    let config_path = cli
+        .config
+        .expect("required unless printing the example config");
+    if cli.check_config {
+        Config::load(&config_path)
+            .with_context(|| format!("invalid configuration {}", config_path.display()))?;
+        println!("configuration {} is valid", config_path.display());
+        return Ok(());
+    }
+
     #[cfg(feature = "otel")]
     let (telemetry_provider, otel_layer) = zkboost_server::otel::init();
     #[cfg(not(feature = "otel"))]
     let otel_layer: Option<tracing_subscriber::layer::Identity> = None;
 
+    // The guard flushes buffered log lines when dropped at exit.
+    let (log_writer, _log_guard) = match &cli.log_dir {
+        Some(dir) => {
+            let rotation = match cli.log_rotation {
+                LogRotation::Hourly => Rotation::HOURLY,
+                LogRotation::Daily => Rotation::DAILY,
+                LogRotation::Never => Rotation::NEVER,
+            };
+            let mut builder = RollingFileAppender::builder()
+                .rotation(rotation)
+                .filename_prefix("zkboost")
+                .filename_suffix("log");
+            if let Some(max_files) = cli.log_max_files {
+                builder = builder.max_log_files(max_files);
+            }
+            tracing_appender::non_blocking(builder.build(dir)?)
+        }
+        None => tracing_appender::non_blocking(std::io::stdout()),
+    };
+    let fmt_layer = tracing_subscriber::fmt::layer()
+        .with_writer(log_writer)
+        .with_ansi(cli.log_dir.is_none());
+    let (text_layer, json_layer) = match cli.log_format {
+        LogFormat::Text => (Some(fmt_layer.compact()), None),
+        LogFormat::Json => (None, Some(fmt_layer.json())),
+    };
+
     tracing_subscriber::registry()
         .with(otel_layer)
-        .with(tracing_subscriber::fmt::layer().compact())
+        .with(text_layer)
+        .with(json_layer)
         .with(EnvFilter::from_default_env())
         .init();
 
-    let cli = Cli::parse();
-
     let metrics = init_metrics();
     spawn_upkeep(metrics.clone());
 
-    let config = Config::load(&cli.config)?;
+    let mut config = Config::load(&config_path)?;
+    config.read_only |= cli.read_only;
     info!(
         port = config.port,
         el_endpoint = %config.el_endpoint,
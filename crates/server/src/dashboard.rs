@@ -227,7 +227,7 @@ impl DashboardMessage {
     ) -> Self {
         let (result, error, proof_size) = match proof_result {
             WorkerProofResult::Ok(bytes) => (ProofResult::Success, None, Some(bytes.len() as u64)),
-            WorkerProofResult::Err(msg) => (ProofResult::Error, Some(msg.clone()), None),
+            WorkerProofResult::Err(_, msg) => (ProofResult::Error, Some(msg.clone()), None),
             WorkerProofResult::Timeout => (ProofResult::Timeout, None, None),
         };
         Self::ProveEnd {
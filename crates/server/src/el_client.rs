@@ -1,29 +1,71 @@
 //! EL JSON-RPC client wrapping `debug_chainConfig`, `eth_getBlockByHash`, and
-//! `debug_executionWitnessByBlockHash` RPC methods.
+//! `debug_executionWitnessByBlockHash` RPC methods, falling back to `debug_executionWitness` on ELs
+//! without the latter.
+
+use std::{
+    sync::atomic::{AtomicBool, Ordering},
+    time::{SystemTime, UNIX_EPOCH},
+};
 
 use alloy_genesis::ChainConfig;
+use alloy_rpc_types_engine::{Claims, JwtError, JwtSecret};
 use reth_ethereum_primitives::{Block, TransactionSigned};
 use serde::{Deserialize, Serialize, de::DeserializeOwned};
 use stateless::ExecutionWitness;
+use tracing::info;
 use url::Url;
 use zkboost_types::Hash256;
 
+use crate::config::RpcAuth;
+
+/// JSON-RPC error code of calls to methods the node does not implement.
+const METHOD_NOT_FOUND: i64 = -32601;
+
 /// Execution layer JSON-RPC client.
 #[derive(Debug)]
 pub struct ElClient {
     url: Url,
     http_client: reqwest::Client,
+    auth: Option<Auth>,
+    /// Set once the EL rejected `debug_executionWitnessByBlockHash` as unknown.
+    witness_by_number: AtomicBool,
+}
+
+/// Resolved [`RpcAuth`], with the JWT secret loaded from disk.
+#[derive(Debug)]
+enum Auth {
+    Bearer(String),
+    Basic {
+        username: String,
+        password: Option<String>,
+    },
+    Jwt(JwtSecret),
 }
 
 impl ElClient {
-    /// Create a new EL client.
-    pub fn new(url: Url) -> Self {
+    /// Create a new EL client sending requests with `http_client`.
+    pub fn new(url: Url, http_client: reqwest::Client) -> Self {
         Self {
             url,
-            http_client: reqwest::Client::new(),
+            http_client,
+            auth: None,
+            witness_by_number: AtomicBool::new(false),
         }
     }
 
+    /// Authenticate every request with `auth`, loading the JWT secret if needed.
+    pub fn with_auth(mut self, auth: &RpcAuth) -> Result<Self, Error> {
+        self.auth = Some(match auth {
+            RpcAuth::Bearer { token } => Auth::Bearer(token.clone()),
+            RpcAuth::Basic { username, password } => Auth::Basic {
+                username: username.clone(),
+                password: password.clone(),
+            },
+            RpcAuth::Jwt { secret_path } => Auth::Jwt(JwtSecret::from_file(secret_path)?),
+        });
+        Ok(self)
+    }
+
     /// Return url of the EL client.
     pub fn url(&self) -> &Url {
         &self.url
@@ -45,12 +87,22 @@ impl ElClient {
             id: 1,
         };
 
-        let response = self
-            .http_client
-            .post(self.url.as_str())
-            .json(&request)
-            .send()
-            .await?;
+        let mut builder = self.http_client.post(self.url.as_str()).json(&request);
+        builder = match &self.auth {
+            None => builder,
+            Some(Auth::Bearer(token)) => builder.bearer_auth(token),
+            Some(Auth::Basic { username, password }) => {
+                builder.basic_auth(username, password.as_ref())
+            }
+            Some(Auth::Jwt(secret)) => {
+                // The Engine API only accepts tokens whose `iat` is within 60 seconds of now.
+                let iat = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map_or(0, |elapsed| elapsed.as_secs());
+                builder.bearer_auth(secret.encode(&Claims { iat, exp: None })?)
+            }
+        };
+        let response = builder.send().await?;
 
         if !response.status().is_success() {
             return Err(Error::Rpc {
@@ -76,6 +128,12 @@ impl ElClient {
         }
     }
 
+    /// Fetch the client version, used as a cheap reachability check.
+    pub async fn get_client_version(&self) -> Result<Option<String>, Error> {
+        let result = self.request("web3_clientVersion", ()).await?;
+        Ok(result.map(|(client_version, _)| client_version))
+    }
+
     /// Fetch chain config.
     pub async fn get_chain_config(&self) -> Result<Option<ChainConfig>, Error> {
         let result = self.request("debug_chainConfig", ()).await?;
@@ -91,12 +149,43 @@ impl ElClient {
     }
 
     /// Fetch execution witness for a block, returning the witness and the raw response size.
+    ///
+    /// ELs without `debug_executionWitnessByBlockHash` are detected on the first call. From then
+    /// on the block number is looked up and the witness fetched with `debug_executionWitness`.
     pub async fn get_execution_witness_by_hash(
         &self,
         block_hash: Hash256,
     ) -> Result<Option<(ExecutionWitness, usize)>, Error> {
-        self.request("debug_executionWitnessByBlockHash", (block_hash,))
-            .await
+        if !self.witness_by_number.load(Ordering::Relaxed) {
+            match self
+                .request("debug_executionWitnessByBlockHash", (block_hash,))
+                .await
+            {
+                Err(Error::Rpc {
+                    code: METHOD_NOT_FOUND,
+                    ..
+                }) => {
+                    info!(
+                        url = %self.url,
+                        "debug_executionWitnessByBlockHash not supported, using debug_executionWitness"
+                    );
+                    self.witness_by_number.store(true, Ordering::Relaxed);
+                }
+                result => return result,
+            }
+        }
+
+        let block: Option<(alloy_rpc_types_eth::Block<TransactionSigned>, _)> = self
+            .request("eth_getBlockByHash", (block_hash, false))
+            .await?;
+        let Some((block, _)) = block else {
+            return Ok(None);
+        };
+        self.request(
+            "debug_executionWitness",
+            (format!("{:#x}", block.header.number),),
+        )
+        .await
     }
 }
 
@@ -136,6 +225,9 @@ pub enum Error {
     /// Response deserialization error.
     #[error("deserialize error: {0}")]
     Deserialize(#[from] serde_json::Error),
+    /// JWT secret could not be loaded or a token could not be signed.
+    #[error("JWT error: {0}")]
+    Jwt(#[from] JwtError),
     /// JSON-RPC level error returned by the node.
     #[error("RPC error {code}: {message}")]
     Rpc {
@@ -145,3 +237,88 @@ pub enum Error {
         message: String,
     },
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        net::Ipv4Addr,
+        sync::{
+            Arc,
+            atomic::{AtomicUsize, Ordering},
+        },
+    };
+
+    use axum::{Json, extract::State};
+    use serde_json::{Value, json};
+    use tokio::net::TcpListener;
+    use zkboost_types::Hash256;
+
+    use super::{ElClient, METHOD_NOT_FOUND};
+
+    /// Mock EL without `debug_executionWitnessByBlockHash`, counting the calls to it.
+    async fn witness_by_number_el(
+        State(by_hash_calls): State<Arc<AtomicUsize>>,
+        Json(request): Json<Value>,
+    ) -> Json<Value> {
+        let zero = |bytes: usize| format!("0x{}", "00".repeat(bytes));
+        let mut response = match request["method"].as_str().unwrap() {
+            "debug_executionWitnessByBlockHash" => {
+                by_hash_calls.fetch_add(1, Ordering::Relaxed);
+                json!({ "error": { "code": METHOD_NOT_FOUND, "message": "method not found" } })
+            }
+            "eth_getBlockByHash" => json!({ "result": {
+                "hash": request["params"][0],
+                "parentHash": zero(32),
+                "sha3Uncles": zero(32),
+                "miner": zero(20),
+                "stateRoot": zero(32),
+                "transactionsRoot": zero(32),
+                "receiptsRoot": zero(32),
+                "logsBloom": zero(256),
+                "difficulty": "0x0",
+                "number": "0x2a",
+                "gasLimit": "0x0",
+                "gasUsed": "0x0",
+                "timestamp": "0x0",
+                "extraData": "0x",
+                "mixHash": zero(32),
+                "nonce": zero(8),
+                "uncles": [],
+                "transactions": [],
+            } }),
+            "debug_executionWitness" => {
+                assert_eq!(request["params"][0], "0x2a");
+                json!({ "result": { "state": [], "codes": [], "keys": [], "headers": [] } })
+            }
+            method => panic!("unexpected method {method}"),
+        };
+        response["jsonrpc"] = json!("2.0");
+        response["id"] = request["id"].clone();
+        Json(response)
+    }
+
+    #[tokio::test]
+    async fn test_witness_falls_back_to_block_number() {
+        let by_hash_calls = Arc::new(AtomicUsize::new(0));
+        let app = axum::Router::new()
+            .route("/", axum::routing::post(witness_by_number_el))
+            .with_state(by_hash_calls.clone());
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap())
+            .parse()
+            .unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        let client = ElClient::new(url, reqwest::Client::new());
+        for _ in 0..2 {
+            let witness = client
+                .get_execution_witness_by_hash(Hash256::repeat_byte(1))
+                .await
+                .unwrap();
+            assert!(witness.is_some());
+            assert!(client.witness_by_number.load(Ordering::Relaxed));
+        }
+        // The unsupported method is only tried once.
+        assert_eq!(by_hash_calls.load(Ordering::Relaxed), 1);
+    }
+}
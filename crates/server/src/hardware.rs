@@ -0,0 +1,160 @@
+//! Host hardware detection, for the hardware requirements of proving backends and `GET /v1/info`.
+//!
+//! Memory is read from `/proc/meminfo` and GPUs are listed with `nvidia-smi`, so both are unknown
+//! on non-Linux hosts and hosts without NVIDIA drivers.
+
+use std::{process::Command, sync::OnceLock};
+
+use zkboost_types::HardwareInfo;
+
+use crate::config::HardwareRequirements;
+
+/// Returns the hardware of this host, detected once.
+pub(crate) fn host() -> &'static HardwareInfo {
+    static HOST: OnceLock<HardwareInfo> = OnceLock::new();
+    HOST.get_or_init(|| HardwareInfo {
+        cpu_cores: std::thread::available_parallelism().map_or(1, |cores| cores.get()),
+        memory_mb: meminfo_mb("MemTotal"),
+        gpu_memory_mb: gpu_memory_mb(),
+    })
+}
+
+/// Returns the currently available host memory in MiB, `None` if it cannot be read.
+pub(crate) fn available_memory_mb() -> Option<u64> {
+    meminfo_mb("MemAvailable")
+}
+
+/// Returns the free memory in MiB of the NVIDIA GPU with the most free memory, `None` without
+/// `nvidia-smi` or GPUs. Runs `nvidia-smi`, so it blocks.
+pub(crate) fn available_gpu_memory_mb() -> Option<u64> {
+    query_gpu_memory_mb("memory.free").into_iter().max()
+}
+
+/// Returns a description of every requirement `hardware` does not meet.
+pub(crate) fn unmet_requirements(
+    hardware: &HardwareInfo,
+    requirements: &HardwareRequirements,
+) -> Vec<String> {
+    let mut unmet = Vec::new();
+    if let Some(cpu_cores) = requirements.cpu_cores
+        && hardware.cpu_cores < cpu_cores
+    {
+        unmet.push(format!(
+            "{cpu_cores} CPU cores required, {} available",
+            hardware.cpu_cores
+        ));
+    }
+    if let Some(memory_mb) = requirements.memory_mb {
+        match hardware.memory_mb {
+            Some(total_mb) if total_mb >= memory_mb => {}
+            Some(total_mb) => unmet.push(format!(
+                "{memory_mb} MiB memory required, {total_mb} MiB installed"
+            )),
+            None => unmet.push(format!(
+                "{memory_mb} MiB memory required, installed memory unknown"
+            )),
+        }
+    }
+    if let Some(gpu_memory_mb) = requirements.gpu_memory_mb {
+        match hardware.gpu_memory_mb.iter().max() {
+            Some(largest_mb) if *largest_mb >= gpu_memory_mb => {}
+            Some(largest_mb) => unmet.push(format!(
+                "a GPU with {gpu_memory_mb} MiB memory required, largest has {largest_mb} MiB"
+            )),
+            None => unmet.push(format!(
+                "a GPU with {gpu_memory_mb} MiB memory required, no GPU detected"
+            )),
+        }
+    }
+    unmet
+}
+
+fn meminfo_mb(key: &str) -> Option<u64> {
+    let meminfo = std::fs::read_to_string("/proc/meminfo").ok()?;
+    parse_meminfo_mb(&meminfo, key)
+}
+
+/// Parses the `key` entry of `/proc/meminfo` content, in MiB.
+fn parse_meminfo_mb(meminfo: &str, key: &str) -> Option<u64> {
+    let kib = meminfo
+        .lines()
+        .find_map(|line| line.strip_prefix(key)?.strip_prefix(':'))?
+        .trim()
+        .strip_suffix("kB")?
+        .trim()
+        .parse::<u64>()
+        .ok()?;
+    Some(kib / 1024)
+}
+
+/// Returns the memory in MiB of every NVIDIA GPU, empty if `nvidia-smi` is not available.
+fn gpu_memory_mb() -> Vec<u64> {
+    query_gpu_memory_mb("memory.total")
+}
+
+/// Returns the `field` memory figure in MiB of every NVIDIA GPU, empty if `nvidia-smi` is not
+/// available.
+fn query_gpu_memory_mb(field: &str) -> Vec<u64> {
+    Command::new("nvidia-smi")
+        .arg(format!("--query-gpu={field}"))
+        .arg("--format=csv,noheader,nounits")
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|stdout| parse_gpu_memory_mb(&stdout))
+        .unwrap_or_default()
+}
+
+fn parse_gpu_memory_mb(stdout: &str) -> Vec<u64> {
+    stdout
+        .lines()
+        .filter_map(|line| line.trim().parse().ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use zkboost_types::HardwareInfo;
+
+    use super::{parse_gpu_memory_mb, parse_meminfo_mb, unmet_requirements};
+    use crate::config::HardwareRequirements;
+
+    #[test]
+    fn test_parse_meminfo_mb() {
+        let meminfo = "MemTotal:       65721380 kB\nMemFree:         1234567 kB\nMemAvailable:   33554432 kB\n";
+        assert_eq!(parse_meminfo_mb(meminfo, "MemAvailable"), Some(32768));
+        assert_eq!(parse_meminfo_mb(meminfo, "MemTotal"), Some(64181));
+        assert_eq!(parse_meminfo_mb("MemTotal: 1 kB\n", "MemAvailable"), None);
+    }
+
+    #[test]
+    fn test_parse_gpu_memory_mb() {
+        assert_eq!(parse_gpu_memory_mb("24564\n81559\n"), [24564, 81559]);
+        assert!(parse_gpu_memory_mb("").is_empty());
+    }
+
+    #[test]
+    fn test_unmet_requirements() {
+        let hardware = HardwareInfo {
+            cpu_cores: 16,
+            memory_mb: Some(65536),
+            gpu_memory_mb: vec![24564],
+        };
+        let requirements = HardwareRequirements {
+            cpu_cores: Some(8),
+            memory_mb: Some(32768),
+            gpu_memory_mb: Some(24000),
+            warn_only: false,
+        };
+        assert!(unmet_requirements(&hardware, &requirements).is_empty());
+
+        let requirements = HardwareRequirements {
+            cpu_cores: Some(32),
+            memory_mb: Some(131072),
+            gpu_memory_mb: Some(81920),
+            warn_only: false,
+        };
+        assert_eq!(unmet_requirements(&hardware, &requirements).len(), 3);
+    }
+}
@@ -0,0 +1,302 @@
+//! Dependency checks for `GET /health?deep=true`: EL JSON-RPC, ere-server health and free disk
+//! space, and the canary check of every program at startup, executing a bundled block.
+
+use std::{
+    collections::HashMap,
+    future::Future,
+    path::Path,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use futures::future::join_all;
+use tokio::time::timeout;
+use url::Url;
+use zkboost_types::{HealthCheck, HealthResponse, ProofType};
+
+use crate::{
+    config::Config,
+    el_client::ElClient,
+    proof::{
+        input::NewPayloadRequestWithWitness,
+        zkvm::{EreServer, zkVMInstance},
+    },
+};
+
+/// Timeout of a single dependency check.
+const CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Suffix of the names of standby ere-server checks.
+const STANDBY_SUFFIX: &str = "/standby";
+
+/// Checks all external dependencies concurrently, the EL only if `el_client` is given, and the
+/// free disk space of the paths in `config`.
+pub(crate) async fn check_dependencies(
+    el_client: Option<&ElClient>,
+    zkvms: &HashMap<ProofType, zkVMInstance>,
+    http_client: &reqwest::Client,
+    config: &Config,
+) -> HealthResponse {
+    let el_check = async {
+        let el_client = el_client?;
+        let check = run_check("el".to_string(), CHECK_TIMEOUT, async {
+            el_client
+                .get_client_version()
+                .await
+                .map(|_| ())
+                .map_err(|error| error.to_string())
+        });
+        Some(check.await)
+    };
+
+    let mut zkvms = zkvms.values().collect::<Vec<_>>();
+    zkvms.sort_by_key(|zkvm| zkvm.proof_type());
+    let ere_checks = zkvms
+        .into_iter()
+        .flat_map(ere_servers)
+        .map(|(name, server)| async move {
+            run_check(
+                format!("ere/{name}"),
+                CHECK_TIMEOUT,
+                check_healthy(http_client, &server.endpoint),
+            )
+            .await
+        });
+
+    let disk_paths = [
+        ("record_dir", &config.record_dir),
+        ("results_path", &config.results_path),
+        ("shutdown_report_path", &config.shutdown_report_path),
+    ];
+    let disk_checks = disk_paths.into_iter().filter_map(|(name, path)| {
+        let path = path.clone()?;
+        let min_free_mb = config.min_free_disk_space_mb;
+        Some(run_check(
+            format!("disk/{name}"),
+            CHECK_TIMEOUT,
+            async move {
+                tokio::task::spawn_blocking(move || check_free_disk_space(&path, min_free_mb))
+                    .await
+                    .map_err(|error| error.to_string())?
+            },
+        ))
+    });
+
+    let (el_check, ere_checks, disk_checks) =
+        tokio::join!(el_check, join_all(ere_checks), join_all(disk_checks));
+    let checks = el_check
+        .into_iter()
+        .chain(ere_checks)
+        .chain(disk_checks)
+        .collect::<Vec<_>>();
+    HealthResponse {
+        healthy: checks.iter().all(|check| check.healthy),
+        checks,
+    }
+}
+
+/// Checks that every program is ready to serve requests, run at startup, returning each check
+/// with the proof type it belongs to.
+///
+/// ere-servers, standby ones included, must report their program as healthy (`GET /health`) and
+/// then execute the `canary` block, which the program must validate. This catches ere-servers
+/// without a program, with a broken one or with the program of another EL. Every check is given
+/// `check_timeout`. Verifier-only backends already parsed their verifying key on creation, and
+/// mock backends have no program.
+pub(crate) async fn check_programs(
+    zkvms: &HashMap<ProofType, zkVMInstance>,
+    http_client: &reqwest::Client,
+    canary: &NewPayloadRequestWithWitness,
+    check_timeout: Duration,
+) -> Vec<(ProofType, HealthCheck)> {
+    let mut zkvms = zkvms.values().collect::<Vec<_>>();
+    zkvms.sort_by_key(|zkvm| zkvm.proof_type());
+    let programs = zkvms.into_iter().flat_map(|zkvm| match zkvm {
+        zkVMInstance::Ere { .. } => ere_servers(zkvm)
+            .into_iter()
+            .map(|(name, server)| (zkvm, name, Some(server)))
+            .collect(),
+        zkVMInstance::Mock { .. } | zkVMInstance::Verifier { .. } => {
+            vec![(zkvm, zkvm.proof_type().to_string(), None)]
+        }
+    });
+    join_all(programs.map(|(zkvm, name, server)| async move {
+        let check = run_check(format!("program/{name}"), check_timeout, async move {
+            let Some(server) = server else {
+                return Ok(());
+            };
+            check_healthy(http_client, &server.endpoint).await?;
+            zkvm.execute_canary(&server, canary)
+                .await
+                .map_err(|error| format!("canary block: {error}"))
+        })
+        .await;
+        (zkvm.proof_type(), check)
+    }))
+    .await
+}
+
+/// Returns the ere-servers of an Ere backend named by proof type, the standby one suffixed with
+/// `/standby`.
+fn ere_servers(zkvm: &zkVMInstance) -> Vec<(String, Arc<EreServer>)> {
+    let zkVMInstance::Ere { servers, .. } = zkvm else {
+        return Vec::new();
+    };
+    let proof_type = zkvm.proof_type();
+    let standby = servers
+        .standby()
+        .map(|server| (format!("{proof_type}{STANDBY_SUFFIX}"), server));
+    [(proof_type.to_string(), servers.active())]
+        .into_iter()
+        .chain(standby)
+        .collect()
+}
+
+/// Returns whether `check` is the check of a standby ere-server.
+pub(crate) fn is_standby_check(check: &HealthCheck) -> bool {
+    check.name.ends_with(STANDBY_SUFFIX)
+}
+
+/// Checks that the ere-server at `endpoint` reports its program healthy (`GET /health`).
+async fn check_healthy(http_client: &reqwest::Client, endpoint: &Url) -> Result<(), String> {
+    let url = endpoint.join("health").map_err(|error| error.to_string())?;
+    http_client
+        .get(url)
+        .send()
+        .await
+        .and_then(reqwest::Response::error_for_status)
+        .map(|_| ())
+        .map_err(|error| error.to_string())
+}
+
+/// Checks that the filesystem of `path` has at least `min_free_mb` MiB available. Paths not
+/// created yet are checked on their closest existing ancestor.
+fn check_free_disk_space(path: &Path, min_free_mb: u64) -> Result<(), String> {
+    let existing = path
+        .ancestors()
+        .find(|ancestor| ancestor.exists())
+        .unwrap_or(Path::new("."));
+    let stat = nix::sys::statvfs::statvfs(existing)
+        .map_err(|error| format!("{}: {error}", existing.display()))?;
+    let free_mb = stat.blocks_available() as u64 * stat.fragment_size() as u64 / (1024 * 1024);
+    if free_mb < min_free_mb {
+        return Err(format!(
+            "{free_mb} MiB free on {}, {min_free_mb} MiB required",
+            existing.display()
+        ));
+    }
+    Ok(())
+}
+
+async fn run_check(
+    name: String,
+    check_timeout: Duration,
+    check: impl Future<Output = Result<(), String>>,
+) -> HealthCheck {
+    let start = Instant::now();
+    let result = match timeout(check_timeout, check).await {
+        Ok(result) => result,
+        Err(_) => Err(format!(
+            "timed out after {} seconds",
+            check_timeout.as_secs()
+        )),
+    };
+    HealthCheck {
+        name,
+        healthy: result.is_ok(),
+        error: result.err(),
+        duration_ms: start.elapsed().as_millis() as u64,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use zkboost_types::ProofType;
+
+    use crate::{
+        config::zkVMConfig,
+        health::{CHECK_TIMEOUT, check_free_disk_space, check_programs, is_standby_check},
+        proof::{input::NewPayloadRequestWithWitness, zkvm::zkVMInstance},
+    };
+
+    #[tokio::test]
+    async fn test_check_programs_fails_unreachable_ere_server() {
+        let http_client = reqwest::Client::new();
+        let config = zkVMConfig::Ere {
+            proof_type: ProofType::RethZisk,
+            proof_timeout_secs: 12,
+            endpoint: "http://127.0.0.1:1".to_string(),
+            image: None,
+            standby_endpoint: None,
+            standby_image: None,
+            shadow_percent: 0,
+            min_available_memory_mb: None,
+            min_available_gpu_memory_mb: None,
+            prove_retries: 0,
+            self_verify: false,
+            requirements: Default::default(),
+            max_block_age_secs: None,
+            resource: Default::default(),
+        };
+        let zkvm = zkVMInstance::new(&config, &http_client).await.unwrap();
+        let zkvms = HashMap::from_iter([(ProofType::RethZisk, zkvm)]);
+
+        let canary = NewPayloadRequestWithWitness::canary().unwrap();
+        let checks = check_programs(&zkvms, &http_client, &canary, CHECK_TIMEOUT).await;
+
+        assert_eq!(checks.len(), 1);
+        let (proof_type, check) = &checks[0];
+        assert_eq!(*proof_type, ProofType::RethZisk);
+        assert_eq!(check.name, "program/reth-zisk");
+        assert!(!check.healthy);
+        assert!(check.error.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_check_programs_checks_standby_ere_server() {
+        let http_client = reqwest::Client::new();
+        let config = zkVMConfig::Ere {
+            proof_type: ProofType::RethZisk,
+            proof_timeout_secs: 12,
+            endpoint: "http://127.0.0.1:1".to_string(),
+            image: None,
+            standby_endpoint: Some("http://127.0.0.1:2".to_string()),
+            standby_image: None,
+            shadow_percent: 0,
+            min_available_memory_mb: None,
+            min_available_gpu_memory_mb: None,
+            prove_retries: 0,
+            self_verify: false,
+            requirements: Default::default(),
+            max_block_age_secs: None,
+            resource: Default::default(),
+        };
+        let zkvm = zkVMInstance::new(&config, &http_client).await.unwrap();
+        let zkvms = HashMap::from_iter([(ProofType::RethZisk, zkvm)]);
+
+        let canary = NewPayloadRequestWithWitness::canary().unwrap();
+        let checks = check_programs(&zkvms, &http_client, &canary, CHECK_TIMEOUT).await;
+
+        let names = checks
+            .iter()
+            .map(|(_, check)| check.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["program/reth-zisk", "program/reth-zisk/standby"]);
+        assert!(checks.iter().all(|(_, check)| !check.healthy));
+        assert!(!is_standby_check(&checks[0].1));
+        assert!(is_standby_check(&checks[1].1));
+    }
+
+    #[test]
+    fn test_check_free_disk_space() {
+        let path = std::env::temp_dir()
+            .join("zkboost-missing")
+            .join("results.jsonl");
+
+        assert!(check_free_disk_space(&path, 0).is_ok());
+        let error = check_free_disk_space(&path, u64::MAX).unwrap_err();
+        assert!(error.contains(&std::env::temp_dir().display().to_string()));
+    }
+}
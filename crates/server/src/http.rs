@@ -1,34 +1,63 @@
 //! HTTP service: `AppState`, Axum router with v1 API handlers, Prometheus metrics middleware, and
 //! request tracing.
 
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::HashMap,
+    sync::{Arc, atomic::AtomicBool},
+    time::Duration,
+};
 
 use axum::{
     Router,
-    extract::{DefaultBodyLimit, State},
-    http::StatusCode,
-    middleware,
+    extract::{DefaultBodyLimit, Request, State},
+    http::{Extensions, HeaderMap, Method, StatusCode, Version, header::CONTENT_TYPE},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
     routing::{get, post},
 };
 use bytes::Bytes;
 use lru::LruCache;
 use metrics_exporter_prometheus::PrometheusHandle;
+use rand::{Rng, rng};
 use tokio::sync::{RwLock, broadcast, mpsc};
 use tower::ServiceBuilder;
-use tower_http::{catch_panic::CatchPanicLayer, trace::TraceLayer};
-use zkboost_types::{Hash256, ProofEvent, ProofType};
+use tower_http::{
+    catch_panic::CatchPanicLayer,
+    compression::{CompressionLayer, DefaultPredicate, Predicate},
+    cors::{AllowOrigin, CorsLayer},
+    decompression::RequestDecompressionLayer,
+    trace::TraceLayer,
+};
+use tracing::{Instrument, info_span};
+use zkboost_types::{Hash256, ProofEvent, ProofType, REQUEST_ID_HEADER};
 
 use crate::{
+    config::{Config, CorsConfig, TimeoutConfig},
     dashboard::{DashboardEvent, DashboardState},
+    el_client::ElClient,
     metrics::http_metrics_middleware,
-    proof::{ProofServiceMessage, zkvm::zkVMInstance},
+    proof::{ProofServiceMessage, queue::QueueTracker, zkvm::zkVMInstance},
+    recorder::Recorder,
+    signing::InfoSigner,
 };
 
+mod admin;
 mod dashboard;
+mod health;
 mod v1;
 
+/// Maximum length of client-provided request ids, longer ones are replaced.
+const MAX_REQUEST_ID_LEN: usize = 64;
+
+tokio::task_local! {
+    /// Id of the API request being handled, included in error responses.
+    static REQUEST_ID: String;
+}
+
 /// Shared application state for all HTTP handlers.
 pub(crate) struct AppState {
+    pub(crate) el_client: Arc<ElClient>,
+    pub(crate) http_client: reqwest::Client,
     pub(crate) zkvms: Arc<HashMap<ProofType, zkVMInstance>>,
     pub(crate) proof_cache: Arc<RwLock<LruCache<(Hash256, ProofType), Bytes>>>,
     pub(crate) metrics: PrometheusHandle,
@@ -36,12 +65,25 @@ pub(crate) struct AppState {
     pub(crate) proof_service_tx: mpsc::Sender<ProofServiceMessage>,
     pub(crate) proof_event_rx: broadcast::Receiver<ProofEvent>,
     pub(crate) dashboard_event_rx: broadcast::Receiver<DashboardEvent>,
+    pub(crate) recorder: Option<Recorder>,
+    pub(crate) queue: Arc<QueueTracker>,
+    pub(crate) timeouts: TimeoutConfig,
+    /// Configuration in use with secrets redacted, for `GET /admin/config`.
+    pub(crate) effective_config: Config,
+    /// Whether proof requests are always rejected.
+    pub(crate) read_only: bool,
+    /// Whether new proof requests are rejected while running proofs finish.
+    pub(crate) draining: AtomicBool,
+    /// Key signing `GET /v1/info/signed` responses, if configured.
+    pub(crate) info_signer: Option<InfoSigner>,
 }
 
 impl AppState {
     /// Creates shared application state for the HTTP handlers.
     #[allow(clippy::too_many_arguments)]
     pub(crate) fn new(
+        el_client: Arc<ElClient>,
+        http_client: reqwest::Client,
         zkvms: Arc<HashMap<ProofType, zkVMInstance>>,
         proof_cache: Arc<RwLock<LruCache<(Hash256, ProofType), Bytes>>>,
         metrics: PrometheusHandle,
@@ -49,8 +91,15 @@ impl AppState {
         proof_service_tx: mpsc::Sender<ProofServiceMessage>,
         proof_event_rx: broadcast::Receiver<ProofEvent>,
         dashboard_event_rx: broadcast::Receiver<DashboardEvent>,
+        recorder: Option<Recorder>,
+        queue: Arc<QueueTracker>,
+        timeouts: TimeoutConfig,
+        effective_config: Config,
+        info_signer: Option<InfoSigner>,
     ) -> Self {
         Self {
+            el_client,
+            http_client,
             zkvms,
             proof_cache,
             metrics,
@@ -58,47 +107,190 @@ impl AppState {
             proof_service_tx,
             proof_event_rx,
             dashboard_event_rx,
+            recorder,
+            queue,
+            timeouts,
+            read_only: effective_config.read_only,
+            effective_config,
+            draining: AtomicBool::new(false),
+            info_signer,
         }
     }
 }
 
 /// Builds the Axum router with all endpoints and middleware.
 pub(crate) fn router(state: Arc<AppState>) -> Router {
+    let (api, admin) = routers(state);
+    api.merge(admin)
+}
+
+/// Builds the router of the public API (proof requests, proofs, verification and server info)
+/// and the router of the operational endpoints (queue, health, drain, config, metrics and
+/// dashboard), so they can be served on separate listeners.
+///
+/// A read-only server only serves verification, server info, health and proof retrieval. Proof
+/// requests are rejected with `403`, and the proof event stream, estimates, queue, drain and
+/// switchover are not routed.
+pub(crate) fn routers(state: Arc<AppState>) -> (Router, Router) {
     let api_middleware = ServiceBuilder::new()
         .layer(middleware::from_fn(http_metrics_middleware))
         .layer(TraceLayer::new_for_http())
         .layer(CatchPanicLayer::new())
+        // Request bodies may be gzip or zstd compressed, the body limit applies after
+        // decompression.
+        .layer(RequestDecompressionLayer::new())
         .layer(DefaultBodyLimit::max(1 << 30));
+    let request_timeout = middleware::from_fn_with_state(
+        Duration::from_secs(state.timeouts.request_secs),
+        timeout_middleware,
+    );
+    let verification_timeout = middleware::from_fn_with_state(
+        Duration::from_secs(state.timeouts.verification_secs),
+        timeout_middleware,
+    );
 
-    let api = Router::new()
-        .route(
-            "/v1/execution_proof_requests",
-            post(v1::post_execution_proof_requests).get(v1::get_execution_proof_requests),
-        )
+    let mut proof_requests = post(v1::post_execution_proof_requests);
+    if !state.read_only {
+        proof_requests = proof_requests.get(v1::get_execution_proof_requests);
+    }
+    let mut api = Router::new()
+        .route("/v1/execution_proof_requests", proof_requests)
         .route(
             "/v1/execution_proofs/{new_payload_request_root}/{proof_type}",
-            get(v1::get_execution_proofs),
+            get(v1::get_execution_proofs).layer(proof_compression()),
         )
         .route(
             "/v1/execution_proof_verifications",
-            post(v1::post_execution_proof_verifications),
+            post(v1::post_execution_proof_verifications).layer(verification_timeout),
         )
         .route("/v1/proof_types", get(v1::get_proof_types))
+        .route("/v1/info", get(v1::get_info))
+        .route("/v1/info/signed", get(v1::get_signed_info));
+    if !state.read_only {
+        api = api.route(
+            "/v1/proof_types/{proof_type}/estimate",
+            get(v1::get_proving_time_estimate),
+        );
+    }
+    let api = api
         .fallback(fallback_handler)
-        .layer(api_middleware);
+        .layer(request_timeout.clone())
+        .layer(api_middleware.clone())
+        .layer(middleware::from_fn(request_id_middleware));
 
-    let mut infra = Router::new()
-        .route("/health", get(StatusCode::OK))
+    let mut admin = Router::new();
+    if !state.read_only {
+        admin = admin
+            .route("/v1/queue", get(v1::get_queue))
+            .route("/admin/drain", post(admin::post_drain))
+            .route("/admin/resume", post(admin::post_resume))
+            .route(
+                "/admin/switchover/{proof_type}",
+                post(admin::post_switchover),
+            );
+    }
+    // Health checks, metrics scrapes and the dashboard are polled, so they are kept out of traces
+    // and HTTP metrics.
+    let mut admin = admin
+        .route("/admin/config", get(admin::get_config))
+        .layer(request_timeout)
+        .layer(api_middleware)
+        .route("/health", get(health::get_health))
+        .route("/health/ready", get(health::get_ready))
         .route("/metrics", get(get_metrics));
 
     if state.dashboard.is_some() {
-        infra = infra
+        admin = admin
             .route("/dashboard", get(dashboard::get_dashboard))
             .route("/dashboard/state", get(dashboard::get_dashboard_state))
             .route("/dashboard/events", get(dashboard::get_dashboard_events));
     }
+    let admin = admin.layer(middleware::from_fn(request_id_middleware));
+
+    (api.with_state(state.clone()), admin.with_state(state))
+}
+
+/// Compresses proof downloads with gzip or zstd if the client accepts it.
+///
+/// Partial responses are sent uncompressed so `Range` offsets of resumed downloads keep
+/// referring to the proof bytes.
+fn proof_compression() -> CompressionLayer<impl Predicate> {
+    CompressionLayer::new().compress_when(DefaultPredicate::new().and(
+        |status: StatusCode, _: Version, _: &HeaderMap, _: &Extensions| {
+            status != StatusCode::PARTIAL_CONTENT
+        },
+    ))
+}
+
+/// Builds the CORS layer from configuration, `None` if no origin is allowed.
+///
+/// Entries are checked by [`Config::validate`](crate::config::Config), invalid ones are skipped.
+pub(crate) fn cors_layer(config: &CorsConfig) -> Option<CorsLayer> {
+    if config.allowed_origins.is_empty() {
+        return None;
+    }
+    let allow_origin = if config.allowed_origins.iter().any(|origin| origin == "*") {
+        AllowOrigin::any()
+    } else {
+        AllowOrigin::list(
+            config
+                .allowed_origins
+                .iter()
+                .filter_map(|origin| origin.parse().ok()),
+        )
+    };
+    let allow_methods = config
+        .allowed_methods
+        .iter()
+        .filter_map(|method| method.parse().ok())
+        .collect::<Vec<Method>>();
+    Some(
+        CorsLayer::new()
+            .allow_origin(allow_origin)
+            .allow_methods(allow_methods)
+            .allow_headers([CONTENT_TYPE]),
+    )
+}
+
+/// Assigns every request an id, taken from the `x-request-id` header if the client sent a usable
+/// one, so failures can be correlated with server logs.
+///
+/// The id is recorded on the request span, returned in the `x-request-id` response header and
+/// included in [`ErrorResponse`](v1::ErrorResponse) bodies.
+async fn request_id_middleware(request: Request, next: Next) -> Response {
+    let request_id = request
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .filter(|value| !value.is_empty() && value.len() <= MAX_REQUEST_ID_LEN)
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("{:016x}", rng().random::<u64>()));
+    let span = info_span!("request", %request_id);
+    let mut response = REQUEST_ID
+        .scope(request_id.clone(), next.run(request).instrument(span))
+        .await;
+    if let Ok(value) = request_id.parse() {
+        response.headers_mut().insert(REQUEST_ID_HEADER, value);
+    }
+    response
+}
 
-    api.merge(infra).with_state(state)
+/// Fails requests whose handler does not respond within `timeout` with `504 Gateway Timeout`.
+///
+/// Only the time until the response head is counted, so SSE streams are not cut off.
+async fn timeout_middleware(
+    State(timeout): State<Duration>,
+    request: Request,
+    next: Next,
+) -> Response {
+    match tokio::time::timeout(timeout, next.run(request)).await {
+        Ok(response) => response,
+        Err(_) => v1::ErrorResponse::new(
+            StatusCode::GATEWAY_TIMEOUT,
+            format!("request timed out after {} seconds", timeout.as_secs()),
+        )
+        .into_response(),
+    }
 }
 
 async fn fallback_handler() -> v1::ErrorResponse {
@@ -111,20 +303,21 @@ async fn get_metrics(State(state): State<Arc<AppState>>) -> String {
 
 #[cfg(test)]
 pub(crate) mod tests {
-    use std::{collections::HashMap, num::NonZeroUsize, sync::Arc};
+    use std::{collections::HashMap, num::NonZeroUsize, sync::Arc, time::Duration};
 
-    use axum::{body::Body, http::Request};
+    use axum::{Router, body::Body, http::Request, middleware, routing::get};
     use lru::LruCache;
     use metrics_exporter_prometheus::PrometheusBuilder;
     use tokio::sync::{RwLock, broadcast, mpsc};
     use tower::ServiceExt;
-    use zkboost_types::ProofType;
+    use zkboost_types::{Hash256, ProofType};
 
     use crate::{
-        config::{MockProvingTime, zkVMConfig},
+        config::{Config, CorsConfig, MockProvingTime, TimeoutConfig, zkVMConfig},
         dashboard::DashboardState,
-        http::{AppState, router},
-        proof::zkvm::zkVMInstance,
+        el_client::ElClient,
+        http::{AppState, cors_layer, router, routers, timeout_middleware},
+        proof::{queue::QueueTracker, zkvm::zkVMInstance},
     };
 
     pub(crate) async fn mock_app_state() -> Arc<AppState> {
@@ -135,8 +328,10 @@ pub(crate) mod tests {
             mock_proving_time: MockProvingTime::Constant { ms: 10 },
             mock_proof_size: 64,
             mock_failure: false,
+            max_block_age_secs: None,
         };
-        let zkvm = zkVMInstance::new(&mock_config).await.unwrap();
+        let http_client = reqwest::Client::new();
+        let zkvm = zkVMInstance::new(&mock_config, &http_client).await.unwrap();
         let zkvms = Arc::new(HashMap::from_iter([(proof_type, zkvm)]));
 
         let proof_cache = Arc::new(RwLock::new(LruCache::new(NonZeroUsize::new(128).unwrap())));
@@ -148,7 +343,14 @@ pub(crate) mod tests {
         let (_, proof_event_rx) = broadcast::channel(16);
         let (_, dashboard_event_rx) = broadcast::channel(16);
 
+        let el_client = Arc::new(ElClient::new(
+            "http://127.0.0.1:1".parse().unwrap(),
+            http_client.clone(),
+        ));
+
         Arc::new(AppState::new(
+            el_client,
+            http_client,
             zkvms,
             proof_cache,
             metrics,
@@ -156,6 +358,19 @@ pub(crate) mod tests {
             proof_service_tx,
             proof_event_rx,
             dashboard_event_rx,
+            None,
+            Arc::new(QueueTracker::new([proof_type])),
+            TimeoutConfig::default(),
+            toml_edit::de::from_str::<Config>(
+                r#"
+                    el_endpoint = "http://127.0.0.1:1"
+                    [[zkvm]]
+                    kind = "mock"
+                    proof_type = "reth-zisk"
+                "#,
+            )
+            .unwrap(),
+            None,
         ))
     }
 
@@ -195,4 +410,219 @@ pub(crate) mod tests {
         assert_eq!(json["code"], 404);
         assert_eq!(json["message"], "route not found");
     }
+
+    #[tokio::test]
+    async fn test_error_response_carries_request_id() {
+        let state = mock_app_state().await;
+        let app = router(state);
+        let get = |request_id: Option<&str>| {
+            let mut request = Request::builder().uri("/nonexistent");
+            if let Some(request_id) = request_id {
+                request = request.header("x-request-id", request_id);
+            }
+            request.body(Body::empty()).unwrap()
+        };
+
+        let response = app.clone().oneshot(get(Some("abc-123"))).await.unwrap();
+        assert_eq!(response.headers()["x-request-id"], "abc-123");
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["request_id"], "abc-123");
+
+        let response = app.oneshot(get(None)).await.unwrap();
+        let request_id = response.headers()["x-request-id"]
+            .to_str()
+            .unwrap()
+            .to_string();
+        assert_eq!(request_id.len(), 16);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["request_id"], request_id);
+    }
+
+    #[tokio::test]
+    async fn test_admin_endpoints_split_from_api() {
+        let state = mock_app_state().await;
+        let (api, admin) = routers(state);
+        let get = |uri: &str| Request::builder().uri(uri).body(Body::empty()).unwrap();
+
+        let response = api.clone().oneshot(get("/metrics")).await.unwrap();
+        assert_eq!(response.status(), 404);
+        let response = api.oneshot(get("/v1/proof_types")).await.unwrap();
+        assert_eq!(response.status(), 200);
+
+        let response = admin.clone().oneshot(get("/metrics")).await.unwrap();
+        assert_eq!(response.status(), 200);
+        let response = admin.clone().oneshot(get("/v1/queue")).await.unwrap();
+        assert_eq!(response.status(), 200);
+        let response = admin.oneshot(get("/v1/proof_types")).await.unwrap();
+        assert_eq!(response.status(), 404);
+    }
+
+    #[tokio::test]
+    async fn test_admin_responses_carry_request_id() {
+        let state = mock_app_state().await;
+        let (_, admin) = routers(state);
+
+        for uri in ["/health", "/metrics", "/v1/queue", "/admin/config"] {
+            let response = admin
+                .clone()
+                .oneshot(
+                    Request::builder()
+                        .uri(uri)
+                        .header("x-request-id", "abc-123")
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(response.headers()["x-request-id"], "abc-123", "{uri}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_read_only_routes() {
+        let mut state = mock_app_state().await;
+        Arc::get_mut(&mut state).unwrap().read_only = true;
+        let app = router(state);
+        let request = |method: &str, uri: &str| {
+            Request::builder()
+                .method(method)
+                .uri(uri)
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        for (method, uri, status) in [
+            (
+                "POST",
+                "/v1/execution_proof_requests?proof_types=reth-zisk",
+                403,
+            ),
+            ("GET", "/v1/proof_types", 200),
+            ("GET", "/v1/info", 200),
+            ("GET", "/health", 200),
+            ("GET", "/admin/config", 200),
+        ] {
+            let response = app.clone().oneshot(request(method, uri)).await.unwrap();
+            assert_eq!(response.status(), status, "{method} {uri}");
+        }
+        for (method, uri) in [
+            ("GET", "/v1/execution_proof_requests"),
+            ("GET", "/v1/proof_types/reth-zisk/estimate"),
+            ("GET", "/v1/queue"),
+            ("POST", "/admin/drain"),
+            ("POST", "/admin/resume"),
+            ("POST", "/admin/switchover/reth-zisk"),
+        ] {
+            let response = app.clone().oneshot(request(method, uri)).await.unwrap();
+            assert!(
+                [404, 405].contains(&response.status().as_u16()),
+                "{method} {uri} is routed"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_timeout_returns_504() {
+        let app = Router::new()
+            .route(
+                "/slow",
+                get(|| async {
+                    tokio::time::sleep(Duration::from_secs(60)).await;
+                    "done"
+                }),
+            )
+            .layer(middleware::from_fn_with_state(
+                Duration::from_millis(10),
+                timeout_middleware,
+            ));
+        let response = app
+            .oneshot(Request::builder().uri("/slow").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 504);
+    }
+
+    #[tokio::test]
+    async fn test_cors_preflight() {
+        let cors = CorsConfig {
+            allowed_origins: vec!["http://localhost:8080".to_string()],
+            ..Default::default()
+        };
+        let state = mock_app_state().await;
+        let response = router(state)
+            .layer(cors_layer(&cors).unwrap())
+            .oneshot(
+                Request::builder()
+                    .method("OPTIONS")
+                    .uri("/v1/proof_types")
+                    .header("origin", "http://localhost:8080")
+                    .header("access-control-request-method", "GET")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 200);
+        assert_eq!(
+            response.headers()["access-control-allow-origin"],
+            "http://localhost:8080"
+        );
+    }
+
+    #[test]
+    fn test_cors_disabled_without_origins() {
+        assert!(cors_layer(&CorsConfig::default()).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_proof_download_compression() {
+        let state = mock_app_state().await;
+        let root = Hash256::repeat_byte(1);
+        state
+            .proof_cache
+            .write()
+            .await
+            .put((root, ProofType::RethZisk), vec![0u8; 4096].into());
+        let app = router(state);
+        let get = |range: Option<&str>| {
+            let mut request = Request::builder()
+                .uri(format!("/v1/execution_proofs/{root}/reth-zisk"))
+                .header("accept-encoding", "zstd");
+            if let Some(range) = range {
+                request = request.header("range", range);
+            }
+            request.body(Body::empty()).unwrap()
+        };
+
+        let response = app.clone().oneshot(get(None)).await.unwrap();
+        assert_eq!(response.status(), 200);
+        assert_eq!(response.headers()["content-encoding"], "zstd");
+
+        let response = app.oneshot(get(Some("bytes=1024-"))).await.unwrap();
+        assert_eq!(response.status(), 206);
+        assert!(!response.headers().contains_key("content-encoding"));
+    }
+
+    #[tokio::test]
+    async fn test_unsupported_content_encoding_rejected() {
+        let state = mock_app_state().await;
+        let response = router(state)
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/v1/execution_proof_requests?proof_types=reth-zisk")
+                    .header("content-encoding", "br")
+                    .body(Body::from(vec![0u8; 16]))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 415);
+    }
 }
@@ -0,0 +1,159 @@
+//! Admin handlers: `POST /admin/drain` stops accepting proof requests while queued and running
+//! proofs finish, `POST /admin/resume` accepts them again, `GET /admin/config` returns the
+//! configuration in use, `POST /admin/switchover/{proof_type}` swaps the active and the standby
+//! ere-server of a proof type.
+
+use std::sync::{Arc, atomic::Ordering};
+
+use axum::{Json, extract::State};
+use tracing::{info, instrument};
+use zkboost_types::{DrainStatus, ProofType, SwitchoverResponse};
+
+use crate::{
+    config::{Config, redact_url_password},
+    http::{
+        AppState,
+        v1::{ErrorResponse, Path},
+    },
+    proof::zkvm::zkVMInstance,
+};
+
+/// Starts draining, new proof requests are rejected with `503` until resumed.
+#[instrument(skip_all)]
+pub(crate) async fn post_drain(State(state): State<Arc<AppState>>) -> Json<DrainStatus> {
+    if !state.draining.swap(true, Ordering::Relaxed) {
+        info!("draining, new proof requests are rejected");
+    }
+    Json(drain_status(&state))
+}
+
+/// Stops draining, new proof requests are accepted again.
+#[instrument(skip_all)]
+pub(crate) async fn post_resume(State(state): State<Arc<AppState>>) -> Json<DrainStatus> {
+    if state.draining.swap(false, Ordering::Relaxed) {
+        info!("resumed accepting proof requests");
+    }
+    Json(drain_status(&state))
+}
+
+/// Returns the configuration in use, with secrets redacted.
+#[instrument(skip_all)]
+pub(crate) async fn get_config(State(state): State<Arc<AppState>>) -> Json<Config> {
+    Json(state.effective_config.clone())
+}
+
+/// Swaps the active and the standby ere-server of `proof_type`. New proofs are sent to the
+/// previous standby right away, proofs already running finish on the previous active one.
+#[instrument(skip_all, fields(%proof_type))]
+pub(crate) async fn post_switchover(
+    State(state): State<Arc<AppState>>,
+    Path(proof_type): Path<ProofType>,
+) -> Result<Json<SwitchoverResponse>, ErrorResponse> {
+    let Some(zkVMInstance::Ere { servers, .. }) = state.zkvms.get(&proof_type) else {
+        return Err(ErrorResponse::not_found(format!(
+            "no ere-server backend for {proof_type}"
+        )));
+    };
+    let Some(active) = servers.switch() else {
+        return Err(ErrorResponse::bad_request(format!(
+            "no standby_endpoint configured for {proof_type}"
+        )));
+    };
+    let mut endpoint = active.endpoint.clone();
+    redact_url_password(&mut endpoint);
+    info!(%proof_type, %endpoint, image = ?active.image, "switched to standby ere-server");
+    Ok(Json(SwitchoverResponse {
+        proof_type,
+        endpoint: endpoint.to_string(),
+        image: active.image.clone(),
+    }))
+}
+
+/// Returns whether the server is draining and how many proofs are still queued or running.
+pub(crate) fn drain_status(state: &AppState) -> DrainStatus {
+    DrainStatus {
+        draining: state.draining.load(Ordering::Relaxed),
+        in_flight: state
+            .queue
+            .to_response()
+            .queues
+            .iter()
+            .map(|queue| queue.queued.len() + usize::from(queue.running.is_some()))
+            .sum(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{
+        body::{Body, to_bytes},
+        http::Request,
+    };
+    use tower::ServiceExt;
+    use zkboost_types::{DrainStatus, Hash256, ProofType};
+
+    use crate::http::{router, tests::mock_app_state};
+
+    #[tokio::test]
+    async fn test_drain_and_resume() {
+        let state = mock_app_state().await;
+        state
+            .queue
+            .enqueue(ProofType::RethZisk, Hash256::repeat_byte(1), 1);
+        let app = router(state);
+        let request = |method: &str, uri: &str| {
+            Request::builder()
+                .method(method)
+                .uri(uri)
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        let response = app
+            .clone()
+            .oneshot(request("GET", "/health/ready"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 200);
+
+        let response = app
+            .clone()
+            .oneshot(request("POST", "/admin/drain"))
+            .await
+            .unwrap();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let status: DrainStatus = serde_json::from_slice(&body).unwrap();
+        assert!(status.draining);
+        assert_eq!(status.in_flight, 1);
+
+        let response = app
+            .clone()
+            .oneshot(request("GET", "/health/ready"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 503);
+
+        app.clone()
+            .oneshot(request("POST", "/admin/resume"))
+            .await
+            .unwrap();
+        let response = app.oneshot(request("GET", "/health/ready")).await.unwrap();
+        assert_eq!(response.status(), 200);
+    }
+
+    #[tokio::test]
+    async fn test_switchover_requires_ere_backend() {
+        let state = mock_app_state().await;
+        let response = router(state)
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/admin/switchover/reth-zisk")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 404);
+    }
+}
@@ -0,0 +1,124 @@
+//! Health check handlers: liveness by default, dependency checks with `?deep=true`, and readiness
+//! to accept proof requests.
+
+use std::sync::{Arc, atomic::Ordering};
+
+use axum::{
+    Json,
+    extract::{Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use serde::Deserialize;
+use tracing::instrument;
+
+use crate::{
+    health::check_dependencies,
+    http::{AppState, admin::drain_status},
+};
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct HealthQuery {
+    #[serde(default)]
+    deep: bool,
+}
+
+/// Returns `200` if the server is up. With `deep=true`, checks the EL, the ere-server endpoints and
+/// the free disk space of configured output paths and returns a per-check report, with `503` if
+/// any check failed. A read-only server never contacts the EL, so it is left out.
+#[instrument(skip_all)]
+pub(crate) async fn get_health(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<HealthQuery>,
+) -> Response {
+    if !query.deep {
+        return StatusCode::OK.into_response();
+    }
+    let el_client = (!state.read_only).then_some(&*state.el_client);
+    let report = check_dependencies(
+        el_client,
+        &state.zkvms,
+        &state.http_client,
+        &state.effective_config,
+    )
+    .await;
+    let status = if report.healthy {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (status, Json(report)).into_response()
+}
+
+/// Returns `200` if the server accepts proof requests and `503` while it is draining, with the
+/// number of proofs still in flight.
+#[instrument(skip_all)]
+pub(crate) async fn get_ready(State(state): State<Arc<AppState>>) -> Response {
+    let status = if state.draining.load(Ordering::Relaxed) {
+        StatusCode::SERVICE_UNAVAILABLE
+    } else {
+        StatusCode::OK
+    };
+    (status, Json(drain_status(&state))).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{
+        body::{Body, to_bytes},
+        http::Request,
+    };
+    use tower::ServiceExt;
+    use zkboost_types::HealthResponse;
+
+    use crate::http::router;
+
+    #[tokio::test]
+    async fn test_deep_health_reports_unreachable_el() {
+        // mock_app_state() points the EL client to a closed port
+        let state = crate::http::tests::mock_app_state().await;
+        let response = router(state)
+            .oneshot(
+                Request::builder()
+                    .uri("/health?deep=true")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), 503);
+
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let report: HealthResponse = serde_json::from_slice(&body).unwrap();
+
+        assert!(!report.healthy);
+        assert_eq!(report.checks.len(), 1);
+        assert_eq!(report.checks[0].name, "el");
+        assert!(!report.checks[0].healthy);
+        assert!(report.checks[0].error.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_deep_health_skips_el_when_read_only() {
+        let mut state = crate::http::tests::mock_app_state().await;
+        std::sync::Arc::get_mut(&mut state).unwrap().read_only = true;
+        let response = router(state)
+            .oneshot(
+                Request::builder()
+                    .uri("/health?deep=true")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), 200);
+
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let report: HealthResponse = serde_json::from_slice(&body).unwrap();
+
+        assert!(report.healthy);
+        assert!(report.checks.iter().all(|check| check.name != "el"));
+    }
+}
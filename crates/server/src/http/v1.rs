@@ -5,6 +5,10 @@
 //! - `GET /execution_proofs/{new_payload_request_root}/{type}`
 //! - `POST /execution_proof_verifications`
 //! - `GET /proof_types`
+//! - `GET /proof_types/{type}/estimate`
+//! - `GET /info`
+//! - `GET /info/signed`
+//! - `GET /queue`
 
 use axum::{
     Json,
@@ -16,13 +20,21 @@ use serde::de::DeserializeOwned;
 
 mod get_execution_proof_requests;
 mod get_execution_proofs;
+mod get_info;
 mod get_proof_types;
+mod get_proving_time_estimate;
+mod get_queue;
+mod get_signed_info;
 mod post_execution_proof_requests;
 mod post_execution_proof_verifications;
 
 pub(crate) use get_execution_proof_requests::get_execution_proof_requests;
 pub(crate) use get_execution_proofs::get_execution_proofs;
+pub(crate) use get_info::get_info;
 pub(crate) use get_proof_types::get_proof_types;
+pub(crate) use get_proving_time_estimate::get_proving_time_estimate;
+pub(crate) use get_queue::get_queue;
+pub(crate) use get_signed_info::get_signed_info;
 pub(crate) use post_execution_proof_requests::post_execution_proof_requests;
 pub(crate) use post_execution_proof_verifications::post_execution_proof_verifications;
 
@@ -33,6 +45,8 @@ pub(crate) struct ErrorResponse {
     code: StatusCode,
     /// Human-readable error message.
     message: String,
+    /// Machine-readable reason, for errors clients are expected to handle.
+    reason: Option<&'static str>,
 }
 
 impl ErrorResponse {
@@ -40,9 +54,15 @@ impl ErrorResponse {
         Self {
             code,
             message: message.into(),
+            reason: None,
         }
     }
 
+    pub(crate) fn with_reason(mut self, reason: &'static str) -> Self {
+        self.reason = Some(reason);
+        self
+    }
+
     pub(crate) fn bad_request(message: impl Into<String>) -> Self {
         Self::new(StatusCode::BAD_REQUEST, message)
     }
@@ -62,6 +82,10 @@ impl IntoResponse for ErrorResponse {
         struct Body {
             code: u16,
             message: String,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            reason: Option<&'static str>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            request_id: Option<String>,
         }
 
         (
@@ -69,6 +93,8 @@ impl IntoResponse for ErrorResponse {
             Json(Body {
                 code: self.code.as_u16(),
                 message: self.message,
+                reason: self.reason,
+                request_id: super::REQUEST_ID.try_with(String::clone).ok(),
             }),
         )
             .into_response()
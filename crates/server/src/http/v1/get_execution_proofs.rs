@@ -1,32 +1,183 @@
 //! Handler for `GET /v1/execution_proofs/{new_payload_request_root}/{proof_type}`.
+//!
+//! Supports single `Range: bytes=start-end` requests so large proofs can be downloaded in parts
+//! or resumed, and sets the `x-proof-sha256` header to the digest of the whole proof.
+//!
+//! With `?wait_secs=`, a proof that is not available yet is waited for (long-polling), so batch
+//! tooling can fetch proofs without following the SSE stream. The wait is capped one second below
+//! the request timeout, so the client gets a 404 instead of a 504; a 404 after a capped wait has
+//! reason `wait_capped` so the client knows to poll again.
 
-use std::sync::Arc;
+use std::{ops::Range, sync::Arc, time::Duration};
 
-use axum::{extract::State, response::IntoResponse};
+use axum::{
+    extract::State,
+    http::{
+        HeaderMap, HeaderValue, StatusCode,
+        header::{ACCEPT_RANGES, CONTENT_RANGE, RANGE},
+    },
+    response::{IntoResponse, Response},
+};
+use bytes::Bytes;
+use sha2::{Digest, Sha256};
+use tokio::{sync::broadcast::error::RecvError, time::timeout};
 use tracing::instrument;
-use zkboost_types::{Hash256, ProofType};
+use zkboost_types::{Hash256, PROOF_SHA256_HEADER, ProofEvent, ProofQuery, ProofType};
 
 use crate::http::{
     AppState,
-    v1::{ErrorResponse, Path},
+    v1::{ErrorResponse, Path, Query},
 };
 
 #[instrument(skip_all)]
 pub(crate) async fn get_execution_proofs(
     State(state): State<Arc<AppState>>,
     Path((new_payload_request_root, proof_type)): Path<(Hash256, ProofType)>,
-) -> Result<impl IntoResponse, ErrorResponse> {
-    match state
+    Query(params): Query<ProofQuery>,
+    headers: HeaderMap,
+) -> Result<Response, ErrorResponse> {
+    // Leave a second of the request timeout to respond with 404 instead of 504.
+    let max_wait = Duration::from_secs(state.timeouts.request_secs.saturating_sub(1));
+    let wait = Duration::from_secs(params.wait_secs.unwrap_or(0));
+    let proof = wait_for_proof(
+        &state,
+        new_payload_request_root,
+        proof_type,
+        wait.min(max_wait),
+        wait > max_wait,
+    )
+    .await?;
+
+    let proof_sha256 = Hash256::from_slice(&Sha256::digest(&proof)).to_string();
+    let common_headers = [
+        (ACCEPT_RANGES, HeaderValue::from_static("bytes")),
+        (
+            PROOF_SHA256_HEADER.parse().expect("valid header name"),
+            HeaderValue::from_str(&proof_sha256).expect("hex is a valid header value"),
+        ),
+    ];
+
+    let Some(range) = headers.get(RANGE) else {
+        return Ok((common_headers, proof).into_response());
+    };
+    let Some(range) = range
+        .to_str()
+        .ok()
+        .and_then(|range| parse_range(range, proof.len()))
+    else {
+        return Err(ErrorResponse::new(
+            StatusCode::RANGE_NOT_SATISFIABLE,
+            format!("unsatisfiable range for proof of {} bytes", proof.len()),
+        ));
+    };
+
+    let content_range = format!("bytes {}-{}/{}", range.start, range.end - 1, proof.len());
+    Ok((
+        StatusCode::PARTIAL_CONTENT,
+        common_headers,
+        [(
+            CONTENT_RANGE,
+            HeaderValue::from_str(&content_range).expect("valid header value"),
+        )],
+        proof.slice(range),
+    )
+        .into_response())
+}
+
+/// Returns the cached proof, waiting up to `wait` for it to complete if it is not cached yet.
+///
+/// `capped` tells that the client asked to wait longer than `wait`, so a proof still missing
+/// when the wait elapses is reported with reason `wait_capped`.
+async fn wait_for_proof(
+    state: &AppState,
+    new_payload_request_root: Hash256,
+    proof_type: ProofType,
+    wait: Duration,
+    capped: bool,
+) -> Result<Bytes, ErrorResponse> {
+    let not_found = || {
+        ErrorResponse::not_found(format!(
+            "proof not found for root {new_payload_request_root} and type {proof_type}"
+        ))
+    };
+    let cached = || cached_proof(state, new_payload_request_root, proof_type);
+
+    if wait.is_zero() {
+        return cached().await.ok_or_else(not_found);
+    }
+    // Subscribe before checking the cache, so a proof completing in between is not missed.
+    let mut proof_event_rx = state.proof_event_rx.resubscribe();
+    if let Some(proof) = cached().await {
+        return Ok(proof);
+    }
+    let completed = timeout(wait, async {
+        loop {
+            match proof_event_rx.recv().await {
+                Ok(ProofEvent::ProofComplete(event))
+                    if event.new_payload_request_root == new_payload_request_root
+                        && event.proof_type == proof_type =>
+                {
+                    break Ok(());
+                }
+                Ok(ProofEvent::ProofFailure(event))
+                    if event.new_payload_request_root == new_payload_request_root
+                        && event.proof_type == proof_type =>
+                {
+                    break Err(ErrorResponse::not_found(format!(
+                        "proof failed for root {new_payload_request_root} and type {proof_type}: {}",
+                        event.error
+                    ))
+                    .with_reason("proof_failed"));
+                }
+                // Missed events might include the completion.
+                Err(RecvError::Lagged(_)) if cached().await.is_some() => break Ok(()),
+                Ok(_) | Err(RecvError::Lagged(_)) => {}
+                Err(RecvError::Closed) => break Ok(()),
+            }
+        }
+    })
+    .await;
+    match completed {
+        Ok(Err(error)) => Err(error),
+        Ok(Ok(())) => cached().await.ok_or_else(not_found),
+        Err(_) if capped => cached().await.ok_or_else(|| {
+            ErrorResponse::not_found(format!(
+                "proof not available for root {new_payload_request_root} and type {proof_type} \
+                 after waiting the maximum of {} seconds",
+                wait.as_secs()
+            ))
+            .with_reason("wait_capped")
+        }),
+        Err(_) => cached().await.ok_or_else(not_found),
+    }
+}
+
+async fn cached_proof(
+    state: &AppState,
+    new_payload_request_root: Hash256,
+    proof_type: ProofType,
+) -> Option<Bytes> {
+    state
         .proof_cache
         .read()
         .await
         .peek(&(new_payload_request_root, proof_type))
-    {
-        Some(proof) => Ok(proof.clone()),
-        None => Err(ErrorResponse::not_found(format!(
-            "proof not found for root {new_payload_request_root} and type {proof_type}"
-        ))),
-    }
+        .cloned()
+}
+
+/// Parses a single `bytes=start-end`, `bytes=start-` or `bytes=-suffix` range into a non-empty
+/// byte range within `len`, `None` if it is malformed or unsatisfiable.
+fn parse_range(range: &str, len: usize) -> Option<Range<usize>> {
+    let (start, end) = range.strip_prefix("bytes=")?.split_once('-')?;
+    let range = match (start.trim(), end.trim()) {
+        ("", suffix) => {
+            let suffix = suffix.parse::<usize>().ok()?.min(len);
+            len - suffix..len
+        }
+        (start, "") => start.parse().ok()?..len,
+        (start, end) => start.parse().ok()?..end.parse::<usize>().ok()?.saturating_add(1).min(len),
+    };
+    (range.start < range.end).then_some(range)
 }
 
 #[cfg(test)]
@@ -40,9 +191,11 @@ mod tests {
         routing::get,
     };
     use bytes::Bytes;
+    use tokio::sync::broadcast;
     use tower::ServiceExt;
     use zkboost_types::{Hash256, ProofType};
 
+    use super::parse_range;
     use crate::http::{AppState, tests::mock_app_state, v1::get_execution_proofs};
 
     fn test_router(state: Arc<AppState>) -> Router {
@@ -119,4 +272,91 @@ mod tests {
         let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
         assert_eq!(body.as_ref(), &[42u8; 64]);
     }
+
+    #[tokio::test]
+    async fn test_proof_range() {
+        let state = mock_app_state().await;
+        let new_payload_request_root = Hash256::from_slice(&[1u8; 32]);
+        let proof = Bytes::from_iter(0u8..64);
+        state
+            .proof_cache
+            .write()
+            .await
+            .put((new_payload_request_root, ProofType::EthrexZisk), proof);
+
+        let response = test_router(state)
+            .oneshot(
+                Request::builder()
+                    .uri(format!(
+                        "/v1/execution_proofs/{new_payload_request_root}/ethrex-zisk"
+                    ))
+                    .header("range", "bytes=60-")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 206);
+        assert_eq!(response.headers()["content-range"], "bytes 60-63/64");
+        assert!(response.headers().contains_key("x-proof-sha256"));
+
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(body.as_ref(), &[60, 61, 62, 63]);
+    }
+
+    #[tokio::test]
+    async fn test_proof_wait_not_found() {
+        let state = mock_app_state().await;
+        let response = test_router(state)
+            .oneshot(
+                Request::builder()
+                    .uri(format!(
+                        "/v1/execution_proofs/{}/ethrex-zisk?wait_secs=1",
+                        Hash256::ZERO
+                    ))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 404);
+    }
+
+    #[tokio::test]
+    async fn test_proof_wait_capped() {
+        let mut state = mock_app_state().await;
+        let (_proof_event_tx, proof_event_rx) = broadcast::channel(16);
+        let app_state = Arc::get_mut(&mut state).unwrap();
+        app_state.timeouts.request_secs = 2;
+        app_state.proof_event_rx = proof_event_rx;
+
+        let response = test_router(state)
+            .oneshot(
+                Request::builder()
+                    .uri(format!(
+                        "/v1/execution_proofs/{}/ethrex-zisk?wait_secs=60",
+                        Hash256::ZERO
+                    ))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 404);
+
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["reason"], "wait_capped");
+    }
+
+    #[test]
+    fn test_parse_range() {
+        assert_eq!(parse_range("bytes=0-9", 64), Some(0..10));
+        assert_eq!(parse_range("bytes=10-", 64), Some(10..64));
+        assert_eq!(parse_range("bytes=-4", 64), Some(60..64));
+        assert_eq!(parse_range("bytes=0-999", 64), Some(0..64));
+        assert_eq!(parse_range("bytes=64-", 64), None);
+        assert_eq!(parse_range("bytes=5-1", 64), None);
+        assert_eq!(parse_range("items=0-1", 64), None);
+    }
 }
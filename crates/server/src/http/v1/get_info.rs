@@ -0,0 +1,97 @@
+//! Handler for `GET /v1/info`.
+
+use std::sync::Arc;
+
+use axum::{Json, extract::State, response::IntoResponse};
+use tracing::instrument;
+use zkboost_types::{BackendInfo, ServerInfoResponse};
+
+use super::ErrorResponse;
+use crate::{hardware, http::AppState};
+
+/// Returns the server version, the host hardware and the prover stack of every configured proof
+/// type.
+#[instrument(skip_all)]
+pub(crate) async fn get_info(
+    State(state): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    Ok(Json(server_info(&state, None)))
+}
+
+/// Builds the server info, including `nonce` if given.
+pub(super) fn server_info(state: &AppState, nonce: Option<String>) -> ServerInfoResponse {
+    let mut backends: Vec<BackendInfo> = state
+        .zkvms
+        .values()
+        .map(|instance| instance.backend_info())
+        .collect();
+
+    // Sort by proof_type for deterministic response order.
+    backends.sort_by_key(|info| info.proof_type);
+
+    ServerInfoResponse {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        git_sha: env!("ZKBOOST_GIT_SHA").to_string(),
+        build_timestamp: env!("ZKBOOST_BUILD_TIMESTAMP").parse().unwrap_or_default(),
+        backends,
+        hardware: Some(hardware::host().clone()),
+        nonce,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::{
+        Router,
+        body::{Body, to_bytes},
+        http::Request,
+        routing::get,
+    };
+    use tower::ServiceExt;
+    use zkboost_types::{BackendKind, ProofType, ServerInfoResponse};
+
+    use crate::http::{AppState, v1::get_info};
+
+    fn test_router(state: Arc<AppState>) -> Router {
+        Router::new()
+            .route("/v1/info", get(get_info))
+            .with_state(state)
+    }
+
+    #[tokio::test]
+    async fn test_info_returns_version_and_backends() {
+        // mock_app_state() creates one RethZisk mock backend
+        let state = crate::http::tests::mock_app_state().await;
+
+        let response = test_router(state)
+            .oneshot(
+                Request::builder()
+                    .uri("/v1/info")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), 200);
+
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let response: ServerInfoResponse = serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(response.version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(response.git_sha, env!("ZKBOOST_GIT_SHA"));
+        assert_eq!(response.backends.len(), 1);
+        assert!(response.hardware.unwrap().cpu_cores >= 1);
+
+        let backend = &response.backends[0];
+        assert_eq!(backend.proof_type, ProofType::RethZisk);
+        assert_eq!(backend.kind, BackendKind::Mock);
+        assert_eq!(backend.ere_version, None);
+        assert_eq!(backend.image, None);
+        assert_eq!(backend.program_vk_sha256, None);
+        assert_eq!(backend.ere_guests_source, None);
+        assert_eq!(backend.program_vk_url, None);
+    }
+}
@@ -0,0 +1,98 @@
+//! Handler for `GET /v1/proof_types/{proof_type}/estimate`.
+
+use std::sync::Arc;
+
+use axum::{
+    Json,
+    extract::{Path, State},
+    response::IntoResponse,
+};
+use tracing::instrument;
+use zkboost_types::{ProofType, ProvingTimeEstimateQuery};
+
+use super::{ErrorResponse, Query};
+use crate::http::AppState;
+
+/// Returns p50/p95 proving time of recent proofs of a proof type, only of blocks in the same gas
+/// bucket if `gas_used` is given.
+#[instrument(skip_all)]
+pub(crate) async fn get_proving_time_estimate(
+    State(state): State<Arc<AppState>>,
+    Path(proof_type): Path<ProofType>,
+    Query(params): Query<ProvingTimeEstimateQuery>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    if !state.zkvms.contains_key(&proof_type) {
+        return Err(ErrorResponse::not_found(format!(
+            "no zkVM configured for proof type '{proof_type}'"
+        )));
+    }
+    Ok(Json(
+        state
+            .queue
+            .proving_time_estimate(proof_type, params.gas_used),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, time::Duration};
+
+    use axum::{
+        Router,
+        body::{Body, to_bytes},
+        http::Request,
+        routing::get,
+    };
+    use tower::ServiceExt;
+    use zkboost_types::{ProofType, ProvingTimeEstimate};
+
+    use crate::http::{AppState, tests::mock_app_state, v1::get_proving_time_estimate};
+
+    fn test_router(state: Arc<AppState>) -> Router {
+        Router::new()
+            .route(
+                "/v1/proof_types/{proof_type}/estimate",
+                get(get_proving_time_estimate),
+            )
+            .with_state(state)
+    }
+
+    #[tokio::test]
+    async fn test_estimate() {
+        let state = mock_app_state().await;
+        state
+            .queue
+            .finish(ProofType::RethZisk, 1_000_000, Some(Duration::from_secs(2)));
+
+        let response = test_router(state)
+            .oneshot(
+                Request::builder()
+                    .uri("/v1/proof_types/reth-zisk/estimate?gas_used=2000000")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 200);
+
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let estimate: ProvingTimeEstimate = serde_json::from_slice(&body).unwrap();
+        assert_eq!(estimate.samples, 1);
+        assert_eq!(estimate.p50_ms, Some(2_000));
+    }
+
+    #[tokio::test]
+    async fn test_estimate_unknown_proof_type() {
+        let state = mock_app_state().await;
+        let response = test_router(state)
+            .oneshot(
+                Request::builder()
+                    .uri("/v1/proof_types/ethrex-zisk/estimate")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 404);
+    }
+}
@@ -0,0 +1,69 @@
+//! Handler for `GET /v1/queue`.
+
+use std::sync::Arc;
+
+use axum::{Json, extract::State, response::IntoResponse};
+use tracing::instrument;
+
+use super::ErrorResponse;
+use crate::http::AppState;
+
+/// Returns the queued and running proofs of every proving backend.
+#[instrument(skip_all)]
+pub(crate) async fn get_queue(
+    State(state): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    Ok(Json(state.queue.to_response()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::{
+        Router,
+        body::{Body, to_bytes},
+        http::Request,
+        routing::get,
+    };
+    use tower::ServiceExt;
+    use zkboost_types::{Hash256, ProofType, QueueResponse};
+
+    use crate::http::{AppState, v1::get_queue};
+
+    fn test_router(state: Arc<AppState>) -> Router {
+        Router::new()
+            .route("/v1/queue", get(get_queue))
+            .with_state(state)
+    }
+
+    #[tokio::test]
+    async fn test_queue_returns_queued_proofs() {
+        let state = crate::http::tests::mock_app_state().await;
+        let root = Hash256::repeat_byte(1);
+        state.queue.enqueue(ProofType::RethZisk, root, 42);
+
+        let response = test_router(state)
+            .oneshot(
+                Request::builder()
+                    .uri("/v1/queue")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), 200);
+
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let response: QueueResponse = serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(response.queues.len(), 1);
+        let queue = &response.queues[0];
+        assert_eq!(queue.proof_type, ProofType::RethZisk);
+        assert!(queue.running.is_none());
+        assert_eq!(queue.queued.len(), 1);
+        assert_eq!(queue.queued[0].new_payload_request_root, root);
+        assert_eq!(queue.queued[0].block_number, 42);
+    }
+}
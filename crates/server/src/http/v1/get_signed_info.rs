@@ -0,0 +1,54 @@
+//! Handler for `GET /v1/info/signed`.
+
+use std::sync::Arc;
+
+use axum::{Json, extract::State};
+use tracing::instrument;
+use zkboost_types::{SignedServerInfo, SignedServerInfoQuery};
+
+use super::{ErrorResponse, Query, get_info::server_info};
+use crate::http::AppState;
+
+/// Returns the server info with the caller's nonce, signed with the configured minisign key.
+#[instrument(skip_all)]
+pub(crate) async fn get_signed_info(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<SignedServerInfoQuery>,
+) -> Result<Json<SignedServerInfo>, ErrorResponse> {
+    let Some(info_signer) = &state.info_signer else {
+        return Err(ErrorResponse::not_found(
+            "server info signing is not configured",
+        ));
+    };
+    let info = serde_json::to_string(&server_info(&state, params.nonce))
+        .map_err(|error| ErrorResponse::internal_server_error(error.to_string()))?;
+    let signature = info_signer
+        .sign(info.as_bytes())
+        .map_err(|error| ErrorResponse::internal_server_error(error.to_string()))?;
+    Ok(Json(SignedServerInfo { info, signature }))
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{Router, body::Body, http::Request, routing::get};
+    use tower::ServiceExt;
+
+    use crate::http::{tests::mock_app_state, v1::get_signed_info};
+
+    #[tokio::test]
+    async fn test_signed_info_not_configured() {
+        let state = mock_app_state().await;
+        let response = Router::new()
+            .route("/v1/info/signed", get(get_signed_info))
+            .with_state(state)
+            .oneshot(
+                Request::builder()
+                    .uri("/v1/info/signed?nonce=abc")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 404);
+    }
+}
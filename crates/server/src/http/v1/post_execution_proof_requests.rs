@@ -1,10 +1,13 @@
 //! Handler for `POST /v1/execution_proof_requests`.
 
-use std::{collections::HashSet, sync::Arc};
+use std::{
+    collections::HashSet,
+    sync::{Arc, atomic::Ordering},
+};
 
-use axum::{Json, extract::State};
+use axum::{Json, extract::State, http::StatusCode};
 use bytes::Bytes;
-use tracing::{debug, info_span, instrument};
+use tracing::{debug, info_span, instrument, warn};
 use zkboost_types::{
     Decode, MainnetEthSpec, NewPayloadRequest, ProofRequestQuery, ProofRequestResponse, TreeHash,
 };
@@ -23,6 +26,22 @@ pub(crate) async fn post_execution_proof_requests(
     Query(params): Query<ProofRequestQuery>,
     body: Bytes,
 ) -> Result<Json<ProofRequestResponse>, ErrorResponse> {
+    if state.read_only {
+        return Err(ErrorResponse::new(
+            StatusCode::FORBIDDEN,
+            "server is read-only, not accepting proof requests",
+        )
+        .with_reason("read_only"));
+    }
+
+    if state.draining.load(Ordering::Relaxed) {
+        return Err(ErrorResponse::new(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "server is draining, not accepting new proof requests",
+        )
+        .with_reason("draining"));
+    }
+
     if params.proof_types.is_empty() {
         return Err(ErrorResponse::bad_request(
             "empty proof types in request".to_string(),
@@ -84,16 +103,34 @@ pub(crate) async fn post_execution_proof_requests(
             ErrorResponse::internal_server_error(format!("failed to enqueue proof: {e}"))
         })?;
 
+    if let Some(recorder) = &state.recorder
+        && let Err(error) = recorder
+            .record(new_payload_request_root, &params.proof_types, &body)
+            .await
+    {
+        warn!(%new_payload_request_root, %error, "proof request recording failed");
+    }
+
     Ok(Json(ProofRequestResponse {
         new_payload_request_root,
+        queue: params
+            .proof_types
+            .iter()
+            .map(|proof_type| state.queue.estimate(*proof_type))
+            .collect(),
     }))
 }
 
 #[cfg(test)]
 mod tests {
-    use std::sync::Arc;
-
-    use axum::{Router, body::Body, http::Request, routing::post};
+    use std::sync::{Arc, atomic::Ordering};
+
+    use axum::{
+        Router,
+        body::{Body, to_bytes},
+        http::Request,
+        routing::post,
+    };
     use tower::ServiceExt;
 
     use crate::http::{AppState, tests::mock_app_state, v1::post_execution_proof_requests};
@@ -107,6 +144,48 @@ mod tests {
             .with_state(state)
     }
 
+    #[tokio::test]
+    async fn test_draining_returns_service_unavailable() {
+        let state = mock_app_state().await;
+        state.draining.store(true, Ordering::Relaxed);
+        let response = test_router(state)
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/v1/execution_proof_requests?proof_types=reth-zisk")
+                    .body(Body::from(vec![0u8; 16]))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 503);
+
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["reason"], "draining");
+    }
+
+    #[tokio::test]
+    async fn test_read_only_returns_forbidden() {
+        let mut state = mock_app_state().await;
+        Arc::get_mut(&mut state).unwrap().read_only = true;
+        let response = test_router(state)
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/v1/execution_proof_requests?proof_types=reth-zisk")
+                    .body(Body::from(vec![0u8; 16]))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 403);
+
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["reason"], "read_only");
+    }
+
     #[tokio::test]
     async fn test_bad_ssz_body() {
         let state = mock_app_state().await;
@@ -5,7 +5,9 @@ use std::{sync::Arc, time::Instant};
 use axum::{Json, extract::State};
 use bytes::Bytes;
 use tracing::{instrument, warn};
-use zkboost_types::{ProofStatus, ProofVerificationQuery, ProofVerificationResponse};
+use zkboost_types::{
+    InvalidProofReason, ProofStatus, ProofVerificationQuery, ProofVerificationResponse,
+};
 
 use crate::{
     http::{
@@ -13,6 +15,7 @@ use crate::{
         v1::{ErrorResponse, Query},
     },
     metrics::record_verify,
+    proof::zkvm::zkVMError,
 };
 
 #[instrument(skip_all)]
@@ -29,20 +32,27 @@ pub(crate) async fn post_execution_proof_verifications(
         ErrorResponse::not_found(format!("unknown proof_type: {proof_type}"))
     })?;
 
-    let status = match zkvm
+    let (status, invalid_reason) = match zkvm
         .verify(params.new_payload_request_root, body.to_vec())
         .await
     {
-        Ok(()) => ProofStatus::Valid,
+        Ok(()) => (ProofStatus::Valid, None),
         Err(e) => {
             warn!(proof_type = %proof_type, error = %e, "verification failed");
-            ProofStatus::Invalid
+            let invalid_reason = match e {
+                zkVMError::PublicValuesMismatch => InvalidProofReason::PublicValuesMismatch,
+                _ => InvalidProofReason::VerificationFailed,
+            };
+            (ProofStatus::Invalid, Some(invalid_reason))
         }
     };
 
     record_verify(proof_type, status.is_valid(), start.elapsed());
 
-    Ok(Json(ProofVerificationResponse { status }))
+    Ok(Json(ProofVerificationResponse {
+        status,
+        invalid_reason,
+    }))
 }
 
 #[cfg(test)]
@@ -56,13 +66,13 @@ mod tests {
         routing::post,
     };
     use tower::ServiceExt;
-    use zkboost_types::{Hash256, ProofStatus, ProofVerificationResponse};
-
-    use crate::{
-        http::{AppState, tests::mock_app_state, v1::post_execution_proof_verifications},
-        proof::zkvm::expected_public_values,
+    use zkboost_types::{
+        Hash256, InvalidProofReason, ProofStatus, ProofVerificationResponse,
+        StatelessValidatorOutput,
     };
 
+    use crate::http::{AppState, tests::mock_app_state, v1::post_execution_proof_verifications};
+
     fn test_router(state: Arc<AppState>) -> Router {
         Router::new()
             .route(
@@ -114,6 +124,7 @@ mod tests {
         let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
         let resp: ProofVerificationResponse = serde_json::from_slice(&body).unwrap();
         assert_eq!(resp.status, ProofStatus::Valid);
+        assert_eq!(resp.invalid_reason, None);
     }
 
     #[tokio::test]
@@ -138,11 +149,47 @@ mod tests {
         let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
         let resp: ProofVerificationResponse = serde_json::from_slice(&body).unwrap();
         assert_eq!(resp.status, ProofStatus::Invalid);
+        assert_eq!(
+            resp.invalid_reason,
+            Some(InvalidProofReason::VerificationFailed)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_mock_proof_of_other_root() {
+        let state = mock_app_state().await;
+        let body = mock_proof(Hash256::repeat_byte(1), 64);
+        let response = test_router(state)
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri(format!(
+                        "/v1/execution_proof_verifications?proof_type=reth-zisk&new_payload_request_root={}",
+                        Hash256::ZERO
+                    ))
+                    .body(Body::from(body))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 200);
+
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let resp: ProofVerificationResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(resp.status, ProofStatus::Invalid);
+        assert_eq!(
+            resp.invalid_reason,
+            Some(InvalidProofReason::PublicValuesMismatch)
+        );
     }
 
     fn mock_proof(new_payload_request_root: Hash256, mock_proof_size: u64) -> Vec<u8> {
         let mut proof = vec![0; mock_proof_size as usize];
-        let public_values = expected_public_values(new_payload_request_root).unwrap();
+        let public_values = StatelessValidatorOutput {
+            new_payload_request_root,
+            successful_validation: true,
+        }
+        .public_values();
         proof[..32].copy_from_slice(&public_values);
         proof
     }
@@ -6,10 +6,16 @@
 pub mod config;
 pub(crate) mod dashboard;
 pub mod el_client;
+pub(crate) mod hardware;
+pub(crate) mod health;
 pub mod http;
 pub mod metrics;
 #[cfg(feature = "otel")]
 pub mod otel;
 pub mod proof;
+pub(crate) mod recorder;
+pub(crate) mod results;
 pub mod server;
+pub(crate) mod shutdown;
+pub(crate) mod signing;
 pub mod witness;
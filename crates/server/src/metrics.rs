@@ -12,7 +12,7 @@ use axum::{
 };
 use metrics::{counter, describe_counter, describe_gauge, describe_histogram, gauge, histogram};
 use metrics_exporter_prometheus::{Matcher, PrometheusBuilder, PrometheusHandle};
-use zkboost_types::ProofType;
+use zkboost_types::{BackendKind, ProofType};
 
 const HTTP_REQUESTS_TOTAL: &str = "zkboost_http_requests_total";
 const HTTP_REQUEST_DURATION_SECONDS: &str = "zkboost_http_request_duration_seconds";
@@ -23,10 +23,21 @@ const WITNESS_FETCH_TOTAL: &str = "zkboost_witness_fetch_total";
 const PROVE_TOTAL: &str = "zkboost_prove_total";
 const PROVE_DURATION_SECONDS: &str = "zkboost_prove_duration_seconds";
 const PROVE_PROOF_BYTES: &str = "zkboost_prove_proof_bytes";
+const PROVE_RETRIES_TOTAL: &str = "zkboost_prove_retries_total";
+const PROVE_VERIFY_FAILURES_TOTAL: &str = "zkboost_prove_verify_failures_total";
+const SHADOW_PROVE_TOTAL: &str = "zkboost_shadow_prove_total";
+const CACHE_EVICTIONS_TOTAL: &str = "zkboost_cache_evictions_total";
+const CACHE_ENTRIES: &str = "zkboost_cache_entries";
+const RECORDINGS_PRUNED_TOTAL: &str = "zkboost_recordings_pruned_total";
+const RECORDINGS_PRUNED_BYTES_TOTAL: &str = "zkboost_recordings_pruned_bytes_total";
+const RECORDINGS: &str = "zkboost_recordings";
+const RECORDINGS_BYTES: &str = "zkboost_recordings_bytes";
+const RECORDING_WRITE_DURATION_SECONDS: &str = "zkboost_recording_write_duration_seconds";
 const VERIFY_TOTAL: &str = "zkboost_verify_total";
 const VERIFY_DURATION_SECONDS: &str = "zkboost_verify_duration_seconds";
 const PROGRAMS_LOADED: &str = "zkboost_programs_loaded";
 const BUILD_INFO: &str = "zkboost_build_info";
+const BACKEND_INFO: &str = "zkboost_backend_info";
 
 const DEFAULT_BUCKETS: &[f64] = &[
     0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
@@ -61,6 +72,38 @@ pub fn init_metrics() -> PrometheusHandle {
     describe_counter!(PROVE_TOTAL, "total prove operations");
     describe_histogram!(PROVE_DURATION_SECONDS, "proof generation duration");
     describe_histogram!(PROVE_PROOF_BYTES, "proof size");
+    describe_counter!(
+        PROVE_RETRIES_TOTAL,
+        "prove retries after transient failures"
+    );
+    describe_counter!(
+        PROVE_VERIFY_FAILURES_TOTAL,
+        "generated proofs failing self-verification"
+    );
+    describe_counter!(
+        SHADOW_PROVE_TOTAL,
+        "shadow proofs on standby ere-servers by outcome"
+    );
+
+    // Cache metrics
+    describe_counter!(CACHE_EVICTIONS_TOTAL, "entries evicted from a full cache");
+    describe_gauge!(CACHE_ENTRIES, "entries held in a cache");
+
+    // Recording metrics
+    describe_counter!(
+        RECORDINGS_PRUNED_TOTAL,
+        "recorded proof requests deleted by retention"
+    );
+    describe_counter!(
+        RECORDINGS_PRUNED_BYTES_TOTAL,
+        "bytes of recorded proof requests deleted"
+    );
+    describe_gauge!(RECORDINGS, "recorded proof requests on disk");
+    describe_gauge!(RECORDINGS_BYTES, "bytes of recorded proof requests on disk");
+    describe_histogram!(
+        RECORDING_WRITE_DURATION_SECONDS,
+        "recorded proof request write duration"
+    );
 
     // Verify operation metrics
     describe_counter!(VERIFY_TOTAL, "total verify operations");
@@ -69,6 +112,7 @@ pub fn init_metrics() -> PrometheusHandle {
     // Application metrics
     describe_gauge!(PROGRAMS_LOADED, "zkvm programs loaded");
     describe_gauge!(BUILD_INFO, "build info");
+    describe_gauge!(BACKEND_INFO, "backend kind per proof type");
 
     handle
 }
@@ -121,6 +165,7 @@ pub fn record_prove(
     counter!(
         PROVE_TOTAL,
         "proof_type" => proof_type.to_string(),
+        "zkvm" => zkvm_label(proof_type),
         "status" => status
     )
     .increment(1);
@@ -128,27 +173,90 @@ pub fn record_prove(
         histogram!(
             PROVE_DURATION_SECONDS,
             "proof_type" => proof_type.to_string(),
+            "zkvm" => zkvm_label(proof_type),
         )
         .record(duration.as_secs_f64());
         histogram!(
             PROVE_PROOF_BYTES,
             "proof_type" => proof_type.to_string(),
+            "zkvm" => zkvm_label(proof_type),
         )
         .record(proof_size as f64);
     }
 }
 
+/// Record a retry of a prove operation after a transient failure.
+pub fn record_prove_retry(proof_type: ProofType) {
+    counter!(
+        PROVE_RETRIES_TOTAL,
+        "proof_type" => proof_type.to_string(),
+        "zkvm" => zkvm_label(proof_type),
+    )
+    .increment(1);
+}
+
+/// Record a generated proof failing self-verification.
+pub fn record_prove_verify_failure(proof_type: ProofType) {
+    counter!(
+        PROVE_VERIFY_FAILURES_TOTAL,
+        "proof_type" => proof_type.to_string(),
+        "zkvm" => zkvm_label(proof_type),
+    )
+    .increment(1);
+}
+
+/// Record a shadow proof, `outcome` telling whether it agreed with the delivered proof.
+pub fn record_shadow_prove(proof_type: ProofType, outcome: &'static str) {
+    counter!(
+        SHADOW_PROVE_TOTAL,
+        "proof_type" => proof_type.to_string(),
+        "zkvm" => zkvm_label(proof_type),
+        "outcome" => outcome,
+    )
+    .increment(1);
+}
+
+/// Record an insertion into `cache`, counting an eviction if the insertion pushed out a
+/// different entry.
+pub fn record_cache_insert(cache: &'static str, evicted: bool, entries: usize) {
+    if evicted {
+        counter!(CACHE_EVICTIONS_TOTAL, "cache" => cache).increment(1);
+    }
+    gauge!(CACHE_ENTRIES, "cache" => cache).set(entries as f64);
+}
+
+/// Record recorded proof requests deleted by the retention policy.
+pub fn record_recordings_pruned(count: usize, bytes: u64) {
+    counter!(RECORDINGS_PRUNED_TOTAL).increment(count as u64);
+    counter!(RECORDINGS_PRUNED_BYTES_TOTAL).increment(bytes);
+}
+
+/// Record a proof request written to the recording directory.
+pub fn record_recording_written(bytes: u64, duration: Duration) {
+    gauge!(RECORDINGS).increment(1.0);
+    gauge!(RECORDINGS_BYTES).increment(bytes as f64);
+    histogram!(RECORDING_WRITE_DURATION_SECONDS).record(duration.as_secs_f64());
+}
+
+/// Record the number and size of the recorded proof requests on disk.
+pub fn record_recordings_usage(count: usize, bytes: u64) {
+    gauge!(RECORDINGS).set(count as f64);
+    gauge!(RECORDINGS_BYTES).set(bytes as f64);
+}
+
 /// Record a verify operation result.
 pub fn record_verify(proof_type: ProofType, verified: bool, duration: Duration) {
     counter!(
         VERIFY_TOTAL,
         "proof_type" => proof_type.to_string(),
+        "zkvm" => zkvm_label(proof_type),
         "verified" => verified.to_string()
     )
     .increment(1);
     histogram!(
         VERIFY_DURATION_SECONDS,
         "proof_type" => proof_type.to_string(),
+        "zkvm" => zkvm_label(proof_type),
     )
     .record(duration.as_secs_f64());
 }
@@ -158,6 +266,31 @@ pub fn set_programs_loaded(count: usize) {
     gauge!(PROGRAMS_LOADED).set(count as f64);
 }
 
+/// Set the backend info gauge of a proof type, labelled with its zkVM and backend kind so
+/// dashboards can join it onto the per-proof-type metrics.
+pub fn set_backend_info(proof_type: ProofType, backend: BackendKind) {
+    let backend = match backend {
+        BackendKind::Ere => "ere",
+        BackendKind::Mock => "mock",
+        BackendKind::Verifier => "verifier",
+    };
+    gauge!(
+        BACKEND_INFO,
+        "proof_type" => proof_type.to_string(),
+        "zkvm" => zkvm_label(proof_type),
+        "backend" => backend
+    )
+    .set(1.0);
+}
+
+/// Returns the zkVM part of a proof type, e.g. `zisk` for `reth-zisk`.
+fn zkvm_label(proof_type: ProofType) -> &'static str {
+    proof_type
+        .as_str()
+        .split_once('-')
+        .map_or(proof_type.as_str(), |(_, zkvm)| zkvm)
+}
+
 /// Set the build info gauge with version label.
 pub fn set_build_info(version: &str) {
     gauge!(BUILD_INFO, "version" => version.to_string()).set(1.0);
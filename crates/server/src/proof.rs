@@ -2,6 +2,7 @@
 //! (dispatched to per-zkVM worker), and completed (cached in LRU, broadcast via SSE).
 
 pub mod input;
+pub mod queue;
 pub mod verifier;
 pub mod worker;
 pub mod zkvm;
@@ -16,6 +17,7 @@ use alloy_genesis::ChainConfig;
 use bytes::Bytes;
 use input::NewPayloadRequestWithWitness;
 use lru::LruCache;
+use queue::QueueTracker;
 use stateless::ExecutionWitness;
 use tokio::sync::{RwLock, broadcast, mpsc, mpsc::error::TrySendError};
 use tokio_util::sync::CancellationToken;
@@ -23,13 +25,14 @@ use tracing::{Span, debug, error, info, trace, warn};
 use worker::WorkerInput;
 use zkboost_types::{
     FailureReason, Hash256, MainnetEthSpec, NewPayloadRequest, ProofComplete, ProofEvent,
-    ProofFailure, ProofType,
+    ProofFailure, ProofResultRecord, ProofType,
 };
 
 use crate::{
     dashboard::DashboardMessage,
-    metrics::record_prove,
+    metrics::{record_cache_insert, record_prove},
     proof::worker::{ProofResult, WorkerOutput},
+    results::{ResultsSink, now_ms},
     witness::WitnessServiceMessage,
 };
 
@@ -66,6 +69,8 @@ pub(crate) struct ProofService {
     proof_event_tx: broadcast::Sender<ProofEvent>,
     witness_service_tx: mpsc::Sender<WitnessServiceMessage>,
     dashboard_service_tx: mpsc::Sender<DashboardMessage>,
+    results_sink: Option<ResultsSink>,
+    queue: Arc<QueueTracker>,
     pending: HashMap<Hash256, PendingRequest>,
     requested: HashSet<(Hash256, ProofType)>,
 }
@@ -78,6 +83,8 @@ impl ProofService {
         proof_event_tx: broadcast::Sender<ProofEvent>,
         witness_service_tx: mpsc::Sender<WitnessServiceMessage>,
         dashboard_service_tx: mpsc::Sender<DashboardMessage>,
+        results_sink: Option<ResultsSink>,
+        queue: Arc<QueueTracker>,
     ) -> Self {
         Self {
             chain_config,
@@ -85,6 +92,8 @@ impl ProofService {
             proof_event_tx,
             witness_service_tx,
             dashboard_service_tx,
+            results_sink,
+            queue,
             pending: HashMap::new(),
             requested: HashSet::new(),
         }
@@ -122,9 +131,12 @@ impl ProofService {
             new_payload_request_root,
             block_hash,
             block_number,
+            gas_used,
             proof_type,
             proof_result,
             duration,
+            retries,
+            self_verify_failures,
         } = output;
 
         trace!(%block_hash, block_number, "received WorkerOutput");
@@ -134,14 +146,45 @@ impl ProofService {
 
         let dashboard_msg = DashboardMessage::prove_end(block_hash, proof_type, &proof_result);
 
+        if let Some(results_sink) = &mut self.results_sink {
+            let (failure_reason, proof_size) = match &proof_result {
+                ProofResult::Ok(proof) => (None, proof.len() as u64),
+                ProofResult::Err(reason, _) => (Some(*reason), 0),
+                ProofResult::Timeout => (Some(FailureReason::ProvingTimeout), 0),
+            };
+            let record = ProofResultRecord {
+                label: None,
+                server_version: env!("CARGO_PKG_VERSION").to_string(),
+                new_payload_request_root,
+                block_hash,
+                block_number,
+                gas_used,
+                proof_type,
+                failure_reason,
+                proving_time_ms: duration.as_millis() as u64,
+                proof_size,
+                completed_at_ms: now_ms(),
+                retries,
+                self_verify_failures,
+            };
+            if let Err(error) = results_sink.append(record) {
+                warn!(%block_hash, %proof_type, %error, "proof result append failed");
+            }
+        }
+
         match proof_result {
             ProofResult::Ok(proof) => {
                 let proof_size = proof.len();
                 info!(%block_hash, block_number, %proof_type, proof_size, "proved");
-                self.proof_cache
-                    .write()
-                    .await
-                    .put((new_payload_request_root, proof_type), proof);
+                let key = (new_payload_request_root, proof_type);
+                let mut proof_cache = self.proof_cache.write().await;
+                let evicted = proof_cache.push(key, proof);
+                record_cache_insert(
+                    "proof",
+                    evicted.is_some_and(|(evicted_key, _)| evicted_key != key),
+                    proof_cache.len(),
+                );
+                drop(proof_cache);
                 let _ = self.proof_event_tx.send(
                     ProofComplete {
                         new_payload_request_root,
@@ -151,12 +194,12 @@ impl ProofService {
                 );
                 record_prove(proof_type, "success", duration, proof_size);
             }
-            ProofResult::Err(error) => {
-                error!(%block_hash, block_number, %proof_type, %error, "proving failed");
+            ProofResult::Err(reason, error) => {
+                error!(%block_hash, block_number, %proof_type, ?reason, %error, "proving failed");
                 self.fail_request(
                     new_payload_request_root,
                     proof_type,
-                    FailureReason::ProvingError,
+                    reason,
                     error,
                     duration,
                 );
@@ -361,11 +404,14 @@ impl ProofService {
         };
 
         let worker_input = WorkerInput { payload, span };
+        self.queue
+            .enqueue(proof_type, new_payload_request_root, block_number);
         match tx.try_send(worker_input) {
             Ok(()) => {
                 debug!(%block_hash, block_number, %proof_type, "proof dispatched");
             }
             Err(error) => {
+                self.queue.remove(proof_type, new_payload_request_root);
                 let reason = match &error {
                     TrySendError::Full(_) => "worker channel full",
                     TrySendError::Closed(_) => "worker channel closed",
@@ -404,7 +450,12 @@ impl ProofService {
             proof_type,
             match reason {
                 FailureReason::WitnessTimeout | FailureReason::ProvingTimeout => "timeout",
-                FailureReason::ProvingError | FailureReason::InternalError => "error",
+                FailureReason::ProvingError
+                | FailureReason::InternalError
+                | FailureReason::InvalidInput
+                | FailureReason::BackendUnavailable
+                | FailureReason::InsufficientMemory => "error",
+                FailureReason::BlockTooOld => "skipped",
             },
             duration,
             0,
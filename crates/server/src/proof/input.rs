@@ -19,7 +19,7 @@ use ere_guests_stateless_validator_reth::{
 };
 use ere_server_client::Input;
 use stateless::ExecutionWitness;
-use zkboost_types::{ElKind, Hash256, MainnetEthSpec, NewPayloadRequest};
+use zkboost_types::{Decode, ElKind, Hash256, MainnetEthSpec, NewPayloadRequest, TreeHash};
 
 /// Combines a `NewPayloadRequest` with its execution witness and chain config, eagerly computing
 /// the `StatelessInput`.
@@ -55,6 +55,25 @@ impl NewPayloadRequestWithWitness {
         })
     }
 
+    /// Builds the bundled canary block the startup program checks execute, the integration test
+    /// fixture.
+    pub(crate) fn canary() -> anyhow::Result<Self> {
+        let new_payload_request = NewPayloadRequest::<MainnetEthSpec>::from_ssz_bytes(
+            include_bytes!("../../tests/fixture/new_payload_request.ssz"),
+        )
+        .map_err(|error| anyhow::anyhow!("decode canary NewPayloadRequest: {error:?}"))?;
+        let witness: ExecutionWitness =
+            serde_json::from_str(include_str!("../../tests/fixture/execution_witness.json"))?;
+        let chain_config: ChainConfig =
+            serde_json::from_str(include_str!("../../tests/fixture/chain_config.json"))?;
+        Self::new(
+            &new_payload_request,
+            new_payload_request.tree_hash_root(),
+            Arc::new(witness),
+            Arc::new(chain_config),
+        )
+    }
+
     /// Returns tree hash root of `NewPayloadRequest`.
     pub(crate) fn root(&self) -> Hash256 {
         self.new_payload_request_root
@@ -75,6 +94,16 @@ impl NewPayloadRequestWithWitness {
         self.stateless_input.block.number
     }
 
+    /// Returns the block timestamp in seconds.
+    pub(crate) fn timestamp(&self) -> u64 {
+        self.stateless_input.block.timestamp
+    }
+
+    /// Returns the gas used by the block.
+    pub(crate) fn gas_used(&self) -> u64 {
+        self.stateless_input.block.gas_used
+    }
+
     /// Generates zkVM input for the given EL kind.
     pub(crate) fn to_zkvm_input(&self, el_kind: ElKind) -> anyhow::Result<Input> {
         let stdin = match el_kind {
@@ -0,0 +1,309 @@
+//! Per-zkVM queue tracker, recording proofs dispatched to a worker and the one currently being
+//! proven, for `GET /v1/queue`.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use zkboost_types::{
+    Hash256, ProofType, ProofTypeQueue, ProvingTimeEstimate, QueueEstimate, QueueResponse,
+    QueuedProof,
+};
+
+/// Number of recent proving times estimates are based on, per proof type.
+const RECENT_PROVING_TIMES: usize = 256;
+
+/// Width of the gas used buckets proving time estimates are grouped by.
+const GAS_BUCKET_SIZE: u64 = 10_000_000;
+
+#[derive(Debug, Clone, Copy)]
+struct Entry {
+    new_payload_request_root: Hash256,
+    block_number: u64,
+    since: Instant,
+}
+
+impl Entry {
+    fn to_queued_proof(self, now: Instant) -> QueuedProof {
+        QueuedProof {
+            new_payload_request_root: self.new_payload_request_root,
+            block_number: self.block_number,
+            elapsed_ms: now.duration_since(self.since).as_millis() as u64,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Queue {
+    queued: VecDeque<Entry>,
+    running: Option<Entry>,
+    /// Gas used and proving time in milliseconds of the most recent successful proofs, oldest
+    /// first.
+    recent_proving_times: VecDeque<(u64, u64)>,
+}
+
+impl Queue {
+    /// Returns the proving times of recent proofs in the gas bucket of `gas_used`, or of all recent
+    /// proofs if `None`, sorted ascending.
+    fn sorted_proving_ms(&self, gas_used: Option<u64>) -> Vec<u64> {
+        let mut sorted = self
+            .recent_proving_times
+            .iter()
+            .filter(|(recent_gas_used, _)| {
+                gas_used.is_none_or(|gas_used| {
+                    recent_gas_used / GAS_BUCKET_SIZE == gas_used / GAS_BUCKET_SIZE
+                })
+            })
+            .map(|(_, proving_ms)| *proving_ms)
+            .collect::<Vec<_>>();
+        sorted.sort_unstable();
+        sorted
+    }
+}
+
+/// Returns the `q` quantile of ascending `sorted` values.
+fn quantile(sorted: &[u64], q: f64) -> Option<u64> {
+    let index = ((sorted.len() as f64 - 1.0) * q).round() as usize;
+    sorted.get(index).copied()
+}
+
+/// Tracks queued and running proofs of every zkVM worker.
+#[derive(Debug)]
+pub(crate) struct QueueTracker {
+    queues: Mutex<HashMap<ProofType, Queue>>,
+}
+
+impl QueueTracker {
+    /// Creates a tracker with an empty queue for each proof type.
+    pub(crate) fn new(proof_types: impl IntoIterator<Item = ProofType>) -> Self {
+        Self {
+            queues: Mutex::new(
+                proof_types
+                    .into_iter()
+                    .map(|proof_type| (proof_type, Queue::default()))
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Records a proof dispatched to the worker of `proof_type`.
+    pub(crate) fn enqueue(
+        &self,
+        proof_type: ProofType,
+        new_payload_request_root: Hash256,
+        block_number: u64,
+    ) {
+        let mut queues = self.queues.lock().unwrap();
+        queues
+            .entry(proof_type)
+            .or_default()
+            .queued
+            .push_back(Entry {
+                new_payload_request_root,
+                block_number,
+                since: Instant::now(),
+            });
+    }
+
+    /// Removes a queued proof that could not be dispatched.
+    pub(crate) fn remove(&self, proof_type: ProofType, new_payload_request_root: Hash256) {
+        let mut queues = self.queues.lock().unwrap();
+        if let Some(queue) = queues.get_mut(&proof_type) {
+            queue
+                .queued
+                .retain(|entry| entry.new_payload_request_root != new_payload_request_root);
+        }
+    }
+
+    /// Moves a queued proof to running when the worker picks it up.
+    pub(crate) fn start(
+        &self,
+        proof_type: ProofType,
+        new_payload_request_root: Hash256,
+        block_number: u64,
+    ) {
+        let mut queues = self.queues.lock().unwrap();
+        let queue = queues.entry(proof_type).or_default();
+        queue
+            .queued
+            .retain(|entry| entry.new_payload_request_root != new_payload_request_root);
+        queue.running = Some(Entry {
+            new_payload_request_root,
+            block_number,
+            since: Instant::now(),
+        });
+    }
+
+    /// Clears the running proof of `proof_type` once the worker finished it, recording its
+    /// proving time if it succeeded.
+    pub(crate) fn finish(
+        &self,
+        proof_type: ProofType,
+        gas_used: u64,
+        proving_time: Option<Duration>,
+    ) {
+        let mut queues = self.queues.lock().unwrap();
+        let Some(queue) = queues.get_mut(&proof_type) else {
+            return;
+        };
+        queue.running = None;
+        if let Some(proving_time) = proving_time {
+            if queue.recent_proving_times.len() == RECENT_PROVING_TIMES {
+                queue.recent_proving_times.pop_front();
+            }
+            queue
+                .recent_proving_times
+                .push_back((gas_used, proving_time.as_millis() as u64));
+        }
+    }
+
+    /// Returns p50 and p95 of recent proving times of `proof_type`, restricted to blocks in the
+    /// same gas bucket as `gas_used` if given.
+    pub(crate) fn proving_time_estimate(
+        &self,
+        proof_type: ProofType,
+        gas_used: Option<u64>,
+    ) -> ProvingTimeEstimate {
+        let queues = self.queues.lock().unwrap();
+        let sorted = queues
+            .get(&proof_type)
+            .map(|queue| queue.sorted_proving_ms(gas_used))
+            .unwrap_or_default();
+        ProvingTimeEstimate {
+            proof_type,
+            samples: sorted.len(),
+            p50_ms: quantile(&sorted, 0.5),
+            p95_ms: quantile(&sorted, 0.95),
+        }
+    }
+
+    /// Estimates when a proof of `proof_type` requested now would start proving.
+    pub(crate) fn estimate(&self, proof_type: ProofType) -> QueueEstimate {
+        let queues = self.queues.lock().unwrap();
+        let Some(queue) = queues.get(&proof_type) else {
+            return QueueEstimate {
+                proof_type,
+                proofs_ahead: 0,
+                estimated_start_ms: None,
+            };
+        };
+        let median_ms = quantile(&queue.sorted_proving_ms(None), 0.5);
+        let estimated_start_ms = median_ms.map(|median_ms| {
+            let running_ms = queue.running.map_or(0, |running| {
+                median_ms.saturating_sub(running.since.elapsed().as_millis() as u64)
+            });
+            running_ms + median_ms * queue.queued.len() as u64
+        });
+        QueueEstimate {
+            proof_type,
+            proofs_ahead: queue.queued.len() + usize::from(queue.running.is_some()),
+            estimated_start_ms,
+        }
+    }
+
+    /// Returns a snapshot of all queues, sorted by proof type.
+    pub(crate) fn to_response(&self) -> QueueResponse {
+        let now = Instant::now();
+        let queues = self.queues.lock().unwrap();
+        let mut queues = queues
+            .iter()
+            .map(|(proof_type, queue)| ProofTypeQueue {
+                proof_type: *proof_type,
+                queued: queue
+                    .queued
+                    .iter()
+                    .map(|entry| entry.to_queued_proof(now))
+                    .collect(),
+                running: queue.running.map(|entry| entry.to_queued_proof(now)),
+            })
+            .collect::<Vec<_>>();
+        queues.sort_by_key(|queue| queue.proof_type);
+        QueueResponse { queues }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use zkboost_types::{Hash256, ProofType};
+
+    use super::QueueTracker;
+
+    #[test]
+    fn test_queue_lifecycle() {
+        let tracker = QueueTracker::new([ProofType::RethZisk]);
+        let first = Hash256::repeat_byte(1);
+        let second = Hash256::repeat_byte(2);
+
+        tracker.enqueue(ProofType::RethZisk, first, 1);
+        tracker.enqueue(ProofType::RethZisk, second, 2);
+        tracker.start(ProofType::RethZisk, first, 1);
+
+        let response = tracker.to_response();
+        let queue = &response.queues[0];
+        assert_eq!(
+            queue.running.as_ref().unwrap().new_payload_request_root,
+            first
+        );
+        assert_eq!(queue.queued.len(), 1);
+        assert_eq!(queue.queued[0].new_payload_request_root, second);
+
+        tracker.finish(ProofType::RethZisk, 0, None);
+        tracker.remove(ProofType::RethZisk, second);
+
+        let response = tracker.to_response();
+        assert!(response.queues[0].running.is_none());
+        assert!(response.queues[0].queued.is_empty());
+    }
+
+    #[test]
+    fn test_estimate() {
+        let tracker = QueueTracker::new([ProofType::RethZisk]);
+        let estimate = tracker.estimate(ProofType::RethZisk);
+        assert_eq!(estimate.proofs_ahead, 0);
+        assert_eq!(estimate.estimated_start_ms, None);
+
+        tracker.start(ProofType::RethZisk, Hash256::repeat_byte(1), 1);
+        tracker.finish(ProofType::RethZisk, 0, Some(Duration::from_secs(10)));
+        tracker.start(ProofType::RethZisk, Hash256::repeat_byte(2), 2);
+        tracker.enqueue(ProofType::RethZisk, Hash256::repeat_byte(3), 3);
+
+        let estimate = tracker.estimate(ProofType::RethZisk);
+        assert_eq!(estimate.proofs_ahead, 2);
+        assert!(estimate.estimated_start_ms.unwrap() > 10_000);
+    }
+
+    #[test]
+    fn test_proving_time_estimate() {
+        let tracker = QueueTracker::new([ProofType::RethZisk]);
+        for (gas_used, secs) in [
+            (1_000_000, 1),
+            (2_000_000, 2),
+            (3_000_000, 3),
+            (45_000_000, 40),
+        ] {
+            tracker.finish(
+                ProofType::RethZisk,
+                gas_used,
+                Some(Duration::from_secs(secs)),
+            );
+        }
+        tracker.finish(ProofType::RethZisk, 1_000_000, None);
+
+        let estimate = tracker.proving_time_estimate(ProofType::RethZisk, Some(5_000_000));
+        assert_eq!(estimate.samples, 3);
+        assert_eq!(estimate.p50_ms, Some(2_000));
+        assert_eq!(estimate.p95_ms, Some(3_000));
+
+        let estimate = tracker.proving_time_estimate(ProofType::RethZisk, None);
+        assert_eq!(estimate.samples, 4);
+        assert_eq!(estimate.p95_ms, Some(40_000));
+
+        let estimate = tracker.proving_time_estimate(ProofType::RethZisk, Some(25_000_000));
+        assert_eq!(estimate.samples, 0);
+        assert_eq!(estimate.p50_ms, None);
+    }
+}
@@ -3,28 +3,63 @@
 //! Wraps the per-zkVM `ere-verifier-*` crates so zkboost can verify proofs
 //! without a remote `ere-server` (which loads the full prover circuit). Each
 //! verifier is bound to a specific compiled guest program via its
-//! `program_vk`, downloaded from the URL configured for that proof_type.
+//! `program_vk`, downloaded from the URL configured for that proof_type and
+//! optionally checked against a minisign signature of the program publisher.
 
-use anyhow::Context;
+use anyhow::{Context, ensure};
 use ere_verifier::Verifier;
-use zkboost_types::ProofType;
+use guest_loader::{public_key_id, verify_program_and_signature};
+use sha2::{Digest, Sha256};
+use tracing::info;
+use zkboost_types::{Hash256, ProofType};
 
+/// Creates a verifier from the program verifying key at `url`, downloaded with `http_client`,
+/// returning it together with the SHA-256 of the encoded verifying key.
+///
+/// With `signature` set to `(signature_url, publisher_public_key)`, the verifying key is only
+/// accepted if the signature at `signature_url` is valid for it. With `expected_sha256` set, it is
+/// only accepted with that digest.
 pub(crate) async fn verifier_from_url(
     proof_type: ProofType,
     url: &str,
-) -> anyhow::Result<Verifier> {
-    let encoded_program_vk = download_program_vk(url).await?;
-    Ok(Verifier::new(proof_type.zkvm_kind(), &encoded_program_vk)?)
+    signature: Option<(&str, &str)>,
+    expected_sha256: Option<Hash256>,
+    http_client: &reqwest::Client,
+) -> anyhow::Result<(Verifier, Hash256)> {
+    let encoded_program_vk = download(http_client, url).await?;
+    let program_vk_sha256 = Hash256::from_slice(&Sha256::digest(&encoded_program_vk));
+    if let Some(expected_sha256) = expected_sha256 {
+        ensure!(
+            program_vk_sha256 == expected_sha256,
+            "program_vk from {url} has SHA-256 {program_vk_sha256}, expected {expected_sha256}"
+        );
+    }
+    if let Some((signature_url, publisher_public_key)) = signature {
+        let signature = String::from_utf8(download(http_client, signature_url).await?)
+            .with_context(|| format!("signature from {signature_url} is not UTF-8"))?;
+        verify_program_and_signature(
+            &encoded_program_vk,
+            signature.trim(),
+            publisher_public_key.trim(),
+        )
+        .with_context(|| format!("verify signature of {url} against {signature_url}"))?;
+        let key_id = public_key_id(publisher_public_key.trim())?;
+        info!(%proof_type, key_id, "program_vk signature verified");
+    }
+    let verifier = Verifier::new(proof_type.zkvm_kind(), &encoded_program_vk)?;
+    Ok((verifier, program_vk_sha256))
 }
 
-async fn download_program_vk(url: &str) -> anyhow::Result<Vec<u8>> {
+async fn download(http_client: &reqwest::Client, url: &str) -> anyhow::Result<Vec<u8>> {
     if let Some(path) = url
         .strip_prefix("file://")
         .or_else(|| if url.contains("://") { None } else { Some(url) })
     {
-        return std::fs::read(path).with_context(|| format!("read program_vk from {path}"));
+        return std::fs::read(path).with_context(|| format!("read {path}"));
     }
-    let bytes = reqwest::get(url)
+    let bytes = http_client
+        .get(url)
+        .send()
         .await
         .with_context(|| format!("GET {url}"))?
         .error_for_status()
@@ -35,3 +70,29 @@ async fn download_program_vk(url: &str) -> anyhow::Result<Vec<u8>> {
         .to_vec();
     Ok(bytes)
 }
+
+#[cfg(test)]
+mod tests {
+    use zkboost_types::{Hash256, ProofType};
+
+    use super::verifier_from_url;
+
+    #[tokio::test]
+    async fn test_verifier_from_url_rejects_sha256_mismatch() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("program.vk");
+        std::fs::write(&path, b"program vk").unwrap();
+
+        let error = verifier_from_url(
+            ProofType::RethZisk,
+            path.to_str().unwrap(),
+            None,
+            Some(Hash256::ZERO),
+            &reqwest::Client::new(),
+        )
+        .await
+        .unwrap_err();
+
+        assert!(error.to_string().contains("expected 0x0000"), "{error}");
+    }
+}
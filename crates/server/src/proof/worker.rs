@@ -3,20 +3,42 @@
 
 use std::{
     sync::Arc,
-    time::{Duration, Instant},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 
 use bytes::Bytes;
-use tokio::{sync::mpsc, time::timeout};
+use rand::{Rng, rng};
+use tokio::{
+    sync::{Semaphore, mpsc},
+    time::{sleep, timeout},
+};
 use tokio_util::sync::CancellationToken;
-use tracing::{Instrument, Span, error, info, info_span, record_all};
-use zkboost_types::{Hash256, ProofType};
+use tracing::{Instrument, Span, error, info, info_span, record_all, warn};
+use zkboost_types::{FailureReason, Hash256, ProofType, StatelessValidatorOutput};
 
 use crate::{
     dashboard::DashboardMessage,
-    proof::{input::NewPayloadRequestWithWitness, zkvm::zkVMInstance},
+    hardware,
+    metrics::{record_prove_retry, record_prove_verify_failure, record_shadow_prove},
+    proof::{
+        input::NewPayloadRequestWithWitness,
+        queue::QueueTracker,
+        zkvm::{GeneratedProof, zkVMError, zkVMInstance},
+    },
 };
 
+/// Backoff before the first retry of a transient prove failure, doubled on every retry.
+const PROVE_RETRY_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Upper bound of the backoff between retries of transient prove failures.
+const MAX_PROVE_RETRY_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Backoff before the first re-check of insufficient memory, doubled on every re-check.
+const MEMORY_CHECK_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Upper bound of the backoff between re-checks of insufficient memory.
+const MAX_MEMORY_CHECK_BACKOFF: Duration = Duration::from_secs(30);
+
 /// Input sent to a per-zkVM worker for proof generation.
 pub(crate) struct WorkerInput {
     pub(crate) payload: Arc<NewPayloadRequestWithWitness>,
@@ -29,9 +51,12 @@ pub(crate) struct WorkerOutput {
     pub(crate) new_payload_request_root: Hash256,
     pub(crate) block_hash: Hash256,
     pub(crate) block_number: u64,
+    pub(crate) gas_used: u64,
     pub(crate) proof_type: ProofType,
     pub(crate) proof_result: ProofResult,
     pub(crate) duration: Duration,
+    pub(crate) retries: u32,
+    pub(crate) self_verify_failures: u32,
 }
 
 /// Result of a single proof generation attempt.
@@ -39,22 +64,33 @@ pub(crate) struct WorkerOutput {
 pub(crate) enum ProofResult {
     /// Proof generated successfully.
     Ok(Bytes),
-    /// Proof generation failed with an error message.
-    Err(String),
+    /// Proof generation failed with a classified reason and an error message.
+    Err(FailureReason, String),
     /// Proof generation exceeded the configured timeout.
     Timeout,
 }
 
 /// Runs a per-zkVM worker loop that processes proof requests sequentially.
+///
+/// With a `resource` semaphore, every proof first waits for a permit, so workers sharing it don't
+/// prove at the same time.
 pub(crate) async fn run_worker(
     zkvm: zkVMInstance,
     shutdown: CancellationToken,
     mut worker_input_rx: mpsc::Receiver<WorkerInput>,
     worker_output_tx: mpsc::Sender<WorkerOutput>,
     dashboard_service_tx: mpsc::Sender<DashboardMessage>,
+    queue: Arc<QueueTracker>,
+    resource: Option<Arc<Semaphore>>,
 ) {
     let proof_type = zkvm.proof_type();
     let proof_timeout = zkvm.proof_timeout();
+    let min_available_memory_mb = zkvm.min_available_memory_mb();
+    let min_available_gpu_memory_mb = zkvm.min_available_gpu_memory_mb();
+    let prove_retries = zkvm.prove_retries();
+    let self_verify = zkvm.self_verify();
+    let shadow_percent = zkvm.shadow_percent();
+    let max_block_age = zkvm.max_block_age();
     let otel_name = format!("prove/{proof_type}");
 
     info!(%proof_type, "zkvm worker started");
@@ -74,6 +110,20 @@ pub(crate) async fn run_worker(
         let new_payload_request_root = input.payload.root();
         let block_hash = input.payload.block_hash();
         let block_number = input.payload.block_number();
+        let gas_used = input.payload.gas_used();
+        let timestamp = input.payload.timestamp();
+
+        // Held until the proof is done, the proof stays queued while waiting.
+        let _permit = match &resource {
+            Some(resource) => tokio::select! {
+                biased;
+
+                _ = shutdown.cancelled() => break,
+
+                permit = resource.acquire() => permit.ok(),
+            },
+            None => None,
+        };
 
         info!(%block_hash, %proof_type, "proving");
 
@@ -87,21 +137,77 @@ pub(crate) async fn run_worker(
 
         let _ =
             dashboard_service_tx.try_send(DashboardMessage::prove_start(block_hash, proof_type));
+        queue.start(proof_type, new_payload_request_root, block_number);
 
         let start = Instant::now();
-        let proof_result = match timeout(proof_timeout, zkvm.prove(&input.payload))
-            .instrument(span.clone())
-            .await
+        let mut attempts = ProveAttempts::default();
+        let mut shadow = None;
+        let mut public_values = None;
+        let proof_result = if let Some(error) = check_block_age(timestamp, max_block_age) {
+            warn!(%block_hash, %proof_type, %error, "proof skipped");
+            ProofResult::Err(FailureReason::BlockTooOld, error)
+        } else if let Err(error) = wait_for_memory(
+            || check_available_memory(min_available_memory_mb, min_available_gpu_memory_mb),
+            proof_timeout,
+            block_hash,
+            proof_type,
+        )
+        .await
         {
-            Ok(Ok(proof)) => ProofResult::Ok(Bytes::from(proof)),
-            Ok(Err(error)) => ProofResult::Err(error.to_string()),
-            Err(_) => ProofResult::Timeout,
+            warn!(%block_hash, %proof_type, %error, "proof rejected");
+            ProofResult::Err(FailureReason::InsufficientMemory, error)
+        } else {
+            // Shadow proofs run on the standby ere-server. On a shared resource they wait for it
+            // like other proofs, so they run after the delivered one.
+            if rng().random_range(0..100) < shadow_percent {
+                let zkvm = zkvm.clone();
+                let payload = input.payload.clone();
+                let resource = resource.clone();
+                shadow = Some(tokio::spawn(async move {
+                    let _permit = match &resource {
+                        Some(resource) => resource.acquire().await.ok(),
+                        None => None,
+                    };
+                    let start = Instant::now();
+                    let result = timeout(proof_timeout, zkvm.shadow_prove(&payload)).await;
+                    (result, start.elapsed())
+                }));
+            }
+
+            // Retries and self-verification share the proof timeout with the first attempt and
+            // the wait for memory, and run on the ere-server of the first attempt.
+            let pinned = zkvm.pinned();
+            let prove = prove_with_retries(
+                || pinned.prove(&input.payload),
+                |generated: GeneratedProof| {
+                    pinned.verify(new_payload_request_root, generated.proof)
+                },
+                block_hash,
+                proof_type,
+                prove_retries,
+                self_verify,
+                &mut attempts,
+            );
+            let remaining = proof_timeout.saturating_sub(start.elapsed());
+            match timeout(remaining, prove).instrument(span.clone()).await {
+                Ok(Ok(generated)) => {
+                    public_values = Some(generated.public_values);
+                    ProofResult::Ok(Bytes::from(generated.proof))
+                }
+                Ok(Err(error)) => ProofResult::Err(error.failure_reason(), error.to_string()),
+                Err(_) => ProofResult::Timeout,
+            }
         };
         let duration = start.elapsed();
+        queue.finish(
+            proof_type,
+            gas_used,
+            matches!(proof_result, ProofResult::Ok(_)).then_some(duration),
+        );
 
         match &proof_result {
             ProofResult::Ok(_) => {}
-            ProofResult::Err(error) => {
+            ProofResult::Err(_, error) => {
                 record_all!(&span, otel.status_code = "ERROR", error_reason = error)
             }
             ProofResult::Timeout => {
@@ -109,14 +215,54 @@ pub(crate) async fn run_worker(
             }
         }
 
+        if let Some(shadow) = shadow {
+            // Both proofs agree if they failed, or commit to the same validation output.
+            let output = public_values.map(|public_values| {
+                StatelessValidatorOutput::decode(&public_values, new_payload_request_root)
+            });
+            tokio::spawn(async move {
+                let Ok((result, shadow_duration)) = shadow.await else {
+                    return;
+                };
+                let (shadow_output, shadow_error) = match result {
+                    Ok(Some(Ok(public_values))) => (
+                        Some(StatelessValidatorOutput::decode(
+                            &public_values,
+                            new_payload_request_root,
+                        )),
+                        None,
+                    ),
+                    Ok(Some(Err(error))) => (None, Some(error.to_string())),
+                    Ok(None) => return,
+                    Err(_) => (None, Some("timeout".to_string())),
+                };
+                if output == shadow_output {
+                    info!(
+                        %block_hash, %proof_type, ?output, ?shadow_duration,
+                        "shadow proof agreed"
+                    );
+                    record_shadow_prove(proof_type, "match");
+                } else {
+                    warn!(
+                        %block_hash, %proof_type, ?output, ?shadow_output, ?shadow_error,
+                        ?shadow_duration, "shadow proof disagreed"
+                    );
+                    record_shadow_prove(proof_type, "mismatch");
+                }
+            });
+        }
+
         if let Err(error) = worker_output_tx
             .send(WorkerOutput {
                 new_payload_request_root,
                 block_hash,
                 block_number,
+                gas_used,
                 proof_type,
                 proof_result,
                 duration,
+                retries: attempts.retries,
+                self_verify_failures: attempts.self_verify_failures,
             })
             .await
         {
@@ -126,3 +272,445 @@ pub(crate) async fn run_worker(
 
     info!(%proof_type, "zkvm worker stopped");
 }
+
+/// Retries and self-verification failures of a proof.
+#[derive(Debug, Default)]
+struct ProveAttempts {
+    retries: u32,
+    self_verify_failures: u32,
+}
+
+/// Proves with `prove`, retrying up to `prove_retries` times with exponential backoff while the
+/// backend cannot be reached. With `self_verify`, checks the proof with `verify` and proves once
+/// more if the check fails.
+async fn prove_with_retries<T: Clone, P, V>(
+    mut prove: impl FnMut() -> P,
+    mut verify: impl FnMut(T) -> V,
+    block_hash: Hash256,
+    proof_type: ProofType,
+    prove_retries: u32,
+    self_verify: bool,
+    attempts: &mut ProveAttempts,
+) -> Result<T, zkVMError>
+where
+    P: Future<Output = Result<T, zkVMError>>,
+    V: Future<Output = Result<(), zkVMError>>,
+{
+    loop {
+        let proof = match prove().await {
+            Err(error)
+                if error.failure_reason() == FailureReason::BackendUnavailable
+                    && attempts.retries < prove_retries =>
+            {
+                let backoff = PROVE_RETRY_BACKOFF
+                    .saturating_mul(2u32.saturating_pow(attempts.retries))
+                    .min(MAX_PROVE_RETRY_BACKOFF);
+                attempts.retries += 1;
+                warn!(
+                    %block_hash, %proof_type, %error, retries = attempts.retries, ?backoff,
+                    "retrying prove"
+                );
+                record_prove_retry(proof_type);
+                sleep(backoff).await;
+                continue;
+            }
+            Ok(proof) if self_verify => proof,
+            result => return result,
+        };
+        match verify(proof.clone()).await {
+            Ok(()) => return Ok(proof),
+            Err(error) => {
+                attempts.self_verify_failures += 1;
+                record_prove_verify_failure(proof_type);
+                if attempts.self_verify_failures > 1 {
+                    return Err(error);
+                }
+                warn!(
+                    %block_hash, %proof_type, %error,
+                    "proof failed self-verification, proving again"
+                );
+            }
+        }
+    }
+}
+
+/// Returns an error message if the block with `timestamp` is older than `max_block_age`.
+fn check_block_age(timestamp: u64, max_block_age: Option<Duration>) -> Option<String> {
+    let max_block_age = max_block_age?;
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).ok()?;
+    let block_age = now.saturating_sub(Duration::from_secs(timestamp));
+    (block_age > max_block_age).then(|| {
+        format!(
+            "block too old: {}s old, at most {}s allowed",
+            block_age.as_secs(),
+            max_block_age.as_secs()
+        )
+    })
+}
+
+/// Returns an error message if less than `min_available_memory_mb` MiB of host memory or
+/// `min_available_gpu_memory_mb` MiB of GPU memory is available.
+///
+/// A check is skipped when no minimum is configured or the available memory cannot be read
+/// (non-Linux hosts, hosts without `nvidia-smi`).
+async fn check_available_memory(
+    min_available_memory_mb: Option<u64>,
+    min_available_gpu_memory_mb: Option<u64>,
+) -> Option<String> {
+    if let Some(min_available_memory_mb) = min_available_memory_mb
+        && let Some(available_memory_mb) = hardware::available_memory_mb()
+        && available_memory_mb < min_available_memory_mb
+    {
+        return Some(format!(
+            "insufficient memory: {available_memory_mb} MiB available, \
+             {min_available_memory_mb} MiB required"
+        ));
+    }
+    let min_available_gpu_memory_mb = min_available_gpu_memory_mb?;
+    let available_gpu_memory_mb = tokio::task::spawn_blocking(hardware::available_gpu_memory_mb)
+        .await
+        .ok()
+        .flatten()?;
+    (available_gpu_memory_mb < min_available_gpu_memory_mb).then(|| {
+        format!(
+            "insufficient GPU memory: {available_gpu_memory_mb} MiB available, \
+             {min_available_gpu_memory_mb} MiB required"
+        )
+    })
+}
+
+/// Waits until `check` finds enough memory, re-checking with exponential backoff for at most
+/// `max_wait`. Returns the last error of `check` if memory is still insufficient then.
+async fn wait_for_memory<F>(
+    mut check: impl FnMut() -> F,
+    max_wait: Duration,
+    block_hash: Hash256,
+    proof_type: ProofType,
+) -> Result<(), String>
+where
+    F: Future<Output = Option<String>>,
+{
+    let deadline = tokio::time::Instant::now() + max_wait;
+    let mut backoff = MEMORY_CHECK_BACKOFF;
+    loop {
+        let Some(error) = check().await else {
+            return Ok(());
+        };
+        let remaining = deadline.saturating_duration_since(tokio::time::Instant::now());
+        if remaining.is_zero() {
+            return Err(error);
+        }
+        info!(%block_hash, %proof_type, %error, ?backoff, "waiting for memory");
+        sleep(backoff.min(remaining)).await;
+        backoff = (backoff * 2).min(MAX_MEMORY_CHECK_BACKOFF);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::Cell, sync::Arc, time::Duration};
+
+    use tokio::{
+        sync::{Semaphore, mpsc},
+        time::Instant,
+    };
+    use tokio_util::sync::CancellationToken;
+    use tracing::Span;
+    use zkboost_types::{Hash256, ProofType};
+
+    use super::{
+        ProofResult, ProveAttempts, WorkerInput, check_block_age, prove_with_retries, run_worker,
+        wait_for_memory,
+    };
+    use crate::{
+        config::{MockProvingTime, zkVMConfig},
+        proof::{
+            input::NewPayloadRequestWithWitness,
+            queue::QueueTracker,
+            zkvm::{zkVMError, zkVMInstance},
+        },
+    };
+
+    /// Builds the proof input of the integration test fixture.
+    fn fixture_payload() -> Arc<NewPayloadRequestWithWitness> {
+        Arc::new(NewPayloadRequestWithWitness::canary().unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_gpu_workers_prove_one_at_a_time() {
+        const PROVING_TIME: Duration = Duration::from_millis(1000);
+        let proof_types = [ProofType::RethSP1, ProofType::RethZisk];
+        let http_client = reqwest::Client::new();
+        let shutdown = CancellationToken::new();
+        let gpu = Arc::new(Semaphore::new(1));
+        let queue = Arc::new(QueueTracker::new(proof_types));
+        let (worker_output_tx, mut worker_output_rx) = mpsc::channel(4);
+        let (dashboard_service_tx, _dashboard_service_rx) = mpsc::channel(16);
+
+        let payload = fixture_payload();
+        for proof_type in proof_types {
+            let config = zkVMConfig::Mock {
+                proof_type,
+                proof_timeout_secs: 60,
+                mock_proving_time: MockProvingTime::Constant {
+                    ms: PROVING_TIME.as_millis() as u64,
+                },
+                mock_proof_size: 64,
+                mock_failure: false,
+                max_block_age_secs: None,
+            };
+            let zkvm = zkVMInstance::new(&config, &http_client).await.unwrap();
+            let (worker_input_tx, worker_input_rx) = mpsc::channel(1);
+            tokio::spawn(run_worker(
+                zkvm,
+                shutdown.clone(),
+                worker_input_rx,
+                worker_output_tx.clone(),
+                dashboard_service_tx.clone(),
+                queue.clone(),
+                Some(gpu.clone()),
+            ));
+            worker_input_tx
+                .send(WorkerInput {
+                    payload: payload.clone(),
+                    span: Span::none(),
+                })
+                .await
+                .unwrap();
+        }
+
+        let first = worker_output_rx.recv().await.unwrap();
+        let first_done = Instant::now();
+        let second = worker_output_rx.recv().await.unwrap();
+        let second_done = Instant::now();
+        shutdown.cancel();
+
+        assert!(matches!(first.proof_result, ProofResult::Ok(_)));
+        assert!(matches!(second.proof_result, ProofResult::Ok(_)));
+        assert_ne!(first.proof_type, second.proof_type);
+        // The second proof only started once the first one released the GPU, in parallel both
+        // would complete at about the same time.
+        assert!(second_done - first_done >= PROVING_TIME / 2);
+        assert!(second.duration >= PROVING_TIME);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_prove_retries_unavailable_backend() {
+        let calls = Cell::new(0);
+        let mut attempts = ProveAttempts::default();
+        let start = Instant::now();
+
+        let result = prove_with_retries(
+            || {
+                calls.set(calls.get() + 1);
+                let result = if calls.get() < 3 {
+                    Err(zkVMError::BackendUnavailable(
+                        "connection refused".to_string(),
+                    ))
+                } else {
+                    Ok(vec![1])
+                };
+                async move { result }
+            },
+            |_| async { Ok(()) },
+            Hash256::ZERO,
+            ProofType::RethSP1,
+            2,
+            false,
+            &mut attempts,
+        )
+        .await;
+
+        assert_eq!(result.unwrap(), [1]);
+        assert_eq!(calls.get(), 3);
+        assert_eq!(attempts.retries, 2);
+        // Backoff of 1s and 2s.
+        assert_eq!(start.elapsed(), Duration::from_secs(3));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_prove_retries_exhausted() {
+        let calls = Cell::new(0);
+        let mut attempts = ProveAttempts::default();
+
+        let result = prove_with_retries(
+            || {
+                calls.set(calls.get() + 1);
+                async {
+                    Err(zkVMError::BackendUnavailable(
+                        "connection refused".to_string(),
+                    ))
+                }
+            },
+            |_| async { Ok(()) },
+            Hash256::ZERO,
+            ProofType::RethSP1,
+            2,
+            false,
+            &mut attempts,
+        )
+        .await;
+
+        assert!(matches!(result, Err(zkVMError::BackendUnavailable(_))));
+        assert_eq!(calls.get(), 3);
+        assert_eq!(attempts.retries, 2);
+
+        // Other failures are not retried.
+        let calls = Cell::new(0);
+        let mut attempts = ProveAttempts::default();
+        let result = prove_with_retries(
+            || {
+                calls.set(calls.get() + 1);
+                async { Err(zkVMError::ProvingFailed("guest panicked".to_string())) }
+            },
+            |_| async { Ok(()) },
+            Hash256::ZERO,
+            ProofType::RethSP1,
+            2,
+            false,
+            &mut attempts,
+        )
+        .await;
+
+        assert!(matches!(result, Err(zkVMError::ProvingFailed(_))));
+        assert_eq!(calls.get(), 1);
+        assert_eq!(attempts.retries, 0);
+    }
+
+    #[tokio::test]
+    async fn test_prove_self_verify_failure_proves_again() {
+        let calls = Cell::new(0);
+        let verified = Cell::new(0);
+        let mut attempts = ProveAttempts::default();
+
+        let result = prove_with_retries(
+            || {
+                calls.set(calls.get() + 1);
+                let proof = vec![calls.get()];
+                async move { Ok(proof) }
+            },
+            |proof| {
+                verified.set(verified.get() + 1);
+                let result = if proof == [1] {
+                    Err(zkVMError::PublicValuesMismatch)
+                } else {
+                    Ok(())
+                };
+                async move { result }
+            },
+            Hash256::ZERO,
+            ProofType::RethSP1,
+            0,
+            true,
+            &mut attempts,
+        )
+        .await;
+
+        assert_eq!(result.unwrap(), [2]);
+        assert_eq!((calls.get(), verified.get()), (2, 2));
+        assert_eq!(attempts.self_verify_failures, 1);
+
+        // A second failing proof is not proven again.
+        let calls = Cell::new(0);
+        let mut attempts = ProveAttempts::default();
+        let result = prove_with_retries(
+            || {
+                calls.set(calls.get() + 1);
+                async { Ok(vec![0]) }
+            },
+            |_| async { Err(zkVMError::PublicValuesMismatch) },
+            Hash256::ZERO,
+            ProofType::RethSP1,
+            0,
+            true,
+            &mut attempts,
+        )
+        .await;
+
+        assert!(matches!(result, Err(zkVMError::PublicValuesMismatch)));
+        assert_eq!(calls.get(), 2);
+        assert_eq!(attempts.self_verify_failures, 2);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_prove_retry_backoff_capped() {
+        let calls = Cell::new(0);
+        let mut attempts = ProveAttempts::default();
+
+        let result = prove_with_retries(
+            || {
+                calls.set(calls.get() + 1);
+                async {
+                    Err(zkVMError::BackendUnavailable(
+                        "connection refused".to_string(),
+                    ))
+                }
+            },
+            |_| async { Ok(()) },
+            Hash256::ZERO,
+            ProofType::RethSP1,
+            u32::MAX,
+            false,
+            &mut attempts,
+        );
+        let result = tokio::time::timeout(Duration::from_secs(3600), result).await;
+
+        // Backoffs of 1, 2, 4, 8, 16 and 32 seconds, then of a minute from 63 seconds on.
+        assert!(result.is_err());
+        assert_eq!(attempts.retries, 6 + (3600 - 63) / 60 + 1);
+        assert_eq!(calls.get(), attempts.retries);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_wait_for_memory() {
+        let calls = Cell::new(0);
+        let start = Instant::now();
+
+        let result = wait_for_memory(
+            || {
+                calls.set(calls.get() + 1);
+                let error = (calls.get() < 3).then(|| "insufficient memory".to_string());
+                async { error }
+            },
+            Duration::from_secs(60),
+            Hash256::ZERO,
+            ProofType::RethSP1,
+        )
+        .await;
+
+        // Re-checked after backoffs of 1 and 2 seconds.
+        assert_eq!(result, Ok(()));
+        assert_eq!(calls.get(), 3);
+        assert_eq!(start.elapsed(), Duration::from_secs(3));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_wait_for_memory_gives_up_at_deadline() {
+        let calls = Cell::new(0);
+        let start = Instant::now();
+
+        let result = wait_for_memory(
+            || {
+                calls.set(calls.get() + 1);
+                async { Some("insufficient memory".to_string()) }
+            },
+            Duration::from_secs(10),
+            Hash256::ZERO,
+            ProofType::RethSP1,
+        )
+        .await;
+
+        // Backoffs of 1, 2 and 4 seconds, then the remaining 3 seconds.
+        assert_eq!(result, Err("insufficient memory".to_string()));
+        assert_eq!(calls.get(), 5);
+        assert_eq!(start.elapsed(), Duration::from_secs(10));
+    }
+
+    #[test]
+    fn test_check_block_age() {
+        let max_block_age = Some(Duration::from_secs(60));
+        assert!(check_block_age(0, None).is_none());
+        assert!(check_block_age(0, max_block_age).is_some());
+        assert!(check_block_age(u64::MAX / 2, max_block_age).is_none());
+    }
+}
@@ -1,10 +1,13 @@
 //! zkVM instance management and initialization, supporting external Ere servers via HTTP and
 //! in-process mock instances for testing.
 
-use std::{ops::Deref, sync::Arc, time::Duration};
+use std::{
+    ops::Deref,
+    sync::{Arc, RwLock},
+    time::Duration,
+};
 
 use anyhow::Context;
-use ere_guests_stateless_validator_common::guest::StatelessValidatorOutput;
 use ere_guests_stateless_validator_ethrex::{
     guest::StatelessValidatorEthrexGuest, host::build_eip8025_input,
 };
@@ -19,13 +22,21 @@ use stateless::StatelessInput;
 use tokio::time::{Instant, sleep, sleep_until};
 use tracing::warn;
 use url::Url;
-use zkboost_types::{ElKind, Hash256, ProofType};
+use zkboost_types::{
+    BackendInfo, ElKind, FailureReason, Hash256, ProofType, StatelessValidatorOutput,
+};
 
 use crate::{
-    config::{MockProvingTime, zkVMConfig},
+    config::{MockProvingTime, ProverResource, zkVMConfig},
     proof::{input::NewPayloadRequestWithWitness, verifier::verifier_from_url},
 };
 
+/// Version of the `ere` crates zkboost was built against, extracted from `Cargo.lock`.
+pub(crate) const ERE_VERSION: &str = env!("ZKBOOST_ERE_VERSION");
+
+/// Git source of the `ere-guests` crates zkboost was built against, extracted from `Cargo.lock`.
+pub(crate) const ERE_GUESTS_SOURCE: &str = env!("ZKBOOST_ERE_GUESTS_SOURCE");
+
 #[derive(Debug, thiserror::Error)]
 #[allow(non_camel_case_types)]
 pub(crate) enum zkVMError {
@@ -35,6 +46,120 @@ pub(crate) enum zkVMError {
     /// The public values do not match the expected values.
     #[error("public values mismatch")]
     PublicValuesMismatch,
+    /// The guest input could not be built.
+    #[error("invalid input: {0}")]
+    InvalidInput(String),
+    /// The zkVM backend could not be reached.
+    #[error("backend unavailable: {0}")]
+    BackendUnavailable(String),
+    /// Proof generation failed.
+    #[error("{0}")]
+    ProvingFailed(String),
+}
+
+impl zkVMError {
+    /// Classifies a prove error as [`FailureReason`].
+    pub(crate) fn failure_reason(&self) -> FailureReason {
+        match self {
+            Self::InvalidInput(_) => FailureReason::InvalidInput,
+            Self::BackendUnavailable(_) => FailureReason::BackendUnavailable,
+            Self::VerificationFailed(_) | Self::PublicValuesMismatch | Self::ProvingFailed(_) => {
+                FailureReason::ProvingError
+            }
+        }
+    }
+
+    /// Classifies an ere-server client error, transport failures mean the backend is down.
+    fn from_ere_error(error: anyhow::Error) -> Self {
+        let unreachable = error.chain().any(|cause| {
+            cause
+                .downcast_ref::<reqwest::Error>()
+                .is_some_and(|error| error.is_connect())
+        });
+        if unreachable {
+            Self::BackendUnavailable(format!("{error:#}"))
+        } else {
+            Self::ProvingFailed(format!("{error:#}"))
+        }
+    }
+}
+
+/// Proof generated by a zkVM backend.
+#[derive(Debug, Clone)]
+pub(crate) struct GeneratedProof {
+    /// Compressed proof bytes.
+    pub(crate) proof: Vec<u8>,
+    /// Public values the proof commits to, as reported by the prover.
+    pub(crate) public_values: Vec<u8>,
+}
+
+/// ere-server of an Ere backend.
+#[derive(Debug)]
+pub(crate) struct EreServer {
+    /// Client of the ere-server.
+    pub(crate) client: zkVMClient,
+    /// HTTP endpoint URL of the ere-server.
+    pub(crate) endpoint: Url,
+    /// Docker image the ere-server runs, if configured.
+    pub(crate) image: Option<String>,
+}
+
+impl EreServer {
+    fn new(
+        endpoint: &str,
+        image: Option<String>,
+        http_client: &reqwest::Client,
+    ) -> anyhow::Result<Self> {
+        let endpoint = Url::parse(endpoint)
+            .with_context(|| format!("failed to parse endpoint URL: {endpoint}"))?;
+        #[cfg(feature = "otel")]
+        let middlewares = vec![Box::new(ere_server_client::OtelPropagation) as Box<_>];
+        #[cfg(not(feature = "otel"))]
+        let middlewares = Vec::new();
+        let client = zkVMClient::new(endpoint.clone(), http_client.clone(), middlewares)
+            .with_context(|| format!("failed to create zkVM client for endpoint: {endpoint}"))?;
+        Ok(Self {
+            client,
+            endpoint,
+            image,
+        })
+    }
+}
+
+/// Active ere-server of an Ere backend and the warm standby it can be switched to.
+#[derive(Debug)]
+pub(crate) struct EreServers(RwLock<(Arc<EreServer>, Option<Arc<EreServer>>)>);
+
+impl EreServers {
+    pub(crate) fn new(active: EreServer, standby: Option<EreServer>) -> Self {
+        Self(RwLock::new((Arc::new(active), standby.map(Arc::new))))
+    }
+
+    /// Returns the ere-server new proofs are sent to.
+    pub(crate) fn active(&self) -> Arc<EreServer> {
+        self.0.read().unwrap().0.clone()
+    }
+
+    /// Returns the standby ere-server, if configured.
+    pub(crate) fn standby(&self) -> Option<Arc<EreServer>> {
+        self.0.read().unwrap().1.clone()
+    }
+
+    /// Returns a copy holding the current active and standby ere-server, unaffected by later
+    /// switchovers.
+    fn snapshot(&self) -> Self {
+        Self(RwLock::new(self.0.read().unwrap().clone()))
+    }
+
+    /// Swaps the active and the standby ere-server, returning the new active one. Proofs already
+    /// started finish on the previous one. `None` without a standby.
+    pub(crate) fn switch(&self) -> Option<Arc<EreServer>> {
+        let mut servers = self.0.write().unwrap();
+        let standby = servers.1.take()?;
+        let active = std::mem::replace(&mut servers.0, standby);
+        servers.1 = Some(active);
+        Some(servers.0.clone())
+    }
 }
 
 /// zkVM instance: remote ere-server, in-process mock, or in-process verifier-only.
@@ -47,8 +172,22 @@ pub(crate) enum zkVMInstance {
         proof_type: ProofType,
         /// Timeout for proof generation.
         proof_timeout: Duration,
-        /// Client of external Ere server.
-        client: Arc<zkVMClient>,
+        /// Active ere-server and its warm standby, shared by all clones.
+        servers: Arc<EreServers>,
+        /// Percentage of proofs additionally proven on the standby ere-server.
+        shadow_percent: u8,
+        /// Minimum available host memory in MiB required to start a proof.
+        min_available_memory_mb: Option<u64>,
+        /// Minimum free GPU memory in MiB required to start a proof.
+        min_available_gpu_memory_mb: Option<u64>,
+        /// Number of retries when the ere-server cannot be reached.
+        prove_retries: u32,
+        /// Whether generated proofs are verified before they are published.
+        self_verify: bool,
+        /// Maximum age of a block when proving starts.
+        max_block_age: Option<Duration>,
+        /// Host resource the ere-server proves on.
+        resource: ProverResource,
     },
     /// Mock zkVM for testing.
     Mock {
@@ -58,6 +197,8 @@ pub(crate) enum zkVMInstance {
         proof_timeout: Duration,
         /// Mock zkVM implementation.
         vm: MockzkVM,
+        /// Maximum age of a block when proving starts.
+        max_block_age: Option<Duration>,
     },
     /// In-process verifier-only backend. No `ere-server`, no prover circuit
     /// loaded — just the lightweight `ere-verifier-*` for this proof type.
@@ -67,35 +208,52 @@ pub(crate) enum zkVMInstance {
         proof_type: ProofType,
         /// Verifier implementation, dispatched per proof_type.
         verifier: Arc<Verifier>,
+        /// SHA-256 of the encoded program verifying key.
+        program_vk_sha256: Hash256,
+        /// URL or local path the program verifying key was loaded from.
+        program_vk_url: String,
     },
 }
 
 impl zkVMInstance {
-    /// Creates a new zkVM instance from configuration.
-    pub(crate) async fn new(config: &zkVMConfig) -> anyhow::Result<Self> {
+    /// Creates a new zkVM instance from configuration, talking to ere-servers with `http_client`.
+    pub(crate) async fn new(
+        config: &zkVMConfig,
+        http_client: &reqwest::Client,
+    ) -> anyhow::Result<Self> {
         match config {
             zkVMConfig::Ere {
                 proof_type,
                 proof_timeout_secs,
                 endpoint,
+                image,
+                standby_endpoint,
+                standby_image,
+                shadow_percent,
+                min_available_memory_mb,
+                min_available_gpu_memory_mb,
+                prove_retries,
+                self_verify,
+                requirements: _,
+                max_block_age_secs,
+                resource,
             } => {
-                let endpoint_url = Url::parse(endpoint)
-                    .with_context(|| format!("failed to parse endpoint URL: {endpoint}"))?;
-                let client = {
-                    #[cfg(feature = "otel")]
-                    let middlewares = vec![Box::new(ere_server_client::OtelPropagation) as Box<_>];
-                    #[cfg(not(feature = "otel"))]
-                    let middlewares = Vec::new();
-
-                    zkVMClient::new(endpoint_url.clone(), reqwest::Client::new(), middlewares)
-                        .with_context(|| {
-                            format!("failed to create zkVM client for endpoint: {endpoint_url}")
-                        })?
-                };
+                let active = EreServer::new(endpoint, image.clone(), http_client)?;
+                let standby = standby_endpoint
+                    .as_deref()
+                    .map(|endpoint| EreServer::new(endpoint, standby_image.clone(), http_client))
+                    .transpose()?;
                 Ok(Self::Ere {
                     proof_type: *proof_type,
                     proof_timeout: Duration::from_secs(*proof_timeout_secs),
-                    client: Arc::new(client),
+                    servers: Arc::new(EreServers::new(active, standby)),
+                    shadow_percent: *shadow_percent,
+                    min_available_memory_mb: *min_available_memory_mb,
+                    min_available_gpu_memory_mb: *min_available_gpu_memory_mb,
+                    prove_retries: *prove_retries,
+                    self_verify: *self_verify,
+                    max_block_age: max_block_age_secs.map(Duration::from_secs),
+                    resource: *resource,
                 })
             }
             zkVMConfig::Mock {
@@ -104,6 +262,7 @@ impl zkVMInstance {
                 mock_proving_time,
                 mock_proof_size,
                 mock_failure,
+                max_block_age_secs,
             } => Ok(Self::Mock {
                 proof_type: *proof_type,
                 proof_timeout: Duration::from_secs(*proof_timeout_secs),
@@ -113,49 +272,145 @@ impl zkVMInstance {
                     *mock_proof_size,
                     *mock_failure,
                 ),
+                max_block_age: max_block_age_secs.map(Duration::from_secs),
             }),
             zkVMConfig::Verifier {
                 proof_type,
                 program_vk_url,
+                publisher_public_key,
+                program_vk_signature_url,
+                program_vk_sha256,
             } => {
-                let verifier = verifier_from_url(*proof_type, program_vk_url)
-                    .await
-                    .with_context(|| {
-                        format!("init in-process verifier for {proof_type} from {program_vk_url}")
-                    })?;
+                let signature_url = program_vk_signature_url
+                    .clone()
+                    .unwrap_or_else(|| format!("{program_vk_url}.minisig"));
+                let signature = publisher_public_key
+                    .as_deref()
+                    .map(|publisher_public_key| (signature_url.as_str(), publisher_public_key));
+                let (verifier, program_vk_sha256) = verifier_from_url(
+                    *proof_type,
+                    program_vk_url,
+                    signature,
+                    *program_vk_sha256,
+                    http_client,
+                )
+                .await
+                .with_context(|| {
+                    format!("init in-process verifier for {proof_type} from {program_vk_url}")
+                })?;
                 Ok(Self::Verifier {
                     proof_type: *proof_type,
                     verifier: Arc::new(verifier),
+                    program_vk_sha256,
+                    program_vk_url: program_vk_url.clone(),
                 })
             }
         }
     }
 
-    /// Generates a compressed proof for the given payload, returning raw proof bytes.
+    /// Returns this instance bound to its current ere-servers, so a proof, its retries and its
+    /// self-verification all run on the same ere-server even if a switchover happens meanwhile.
+    pub(crate) fn pinned(&self) -> Self {
+        let mut zkvm = self.clone();
+        if let Self::Ere { servers, .. } = &mut zkvm {
+            *servers = Arc::new(servers.snapshot());
+        }
+        zkvm
+    }
+
+    /// Generates a compressed proof for the given payload.
     pub(crate) async fn prove(
         &self,
         new_payload_request_with_witness: &NewPayloadRequestWithWitness,
-    ) -> anyhow::Result<Vec<u8>> {
+    ) -> Result<GeneratedProof, zkVMError> {
         if let Self::Mock { vm, .. } = self {
-            return vm
+            let proof = vm
                 .prove(new_payload_request_with_witness.stateless_input())
-                .await;
+                .await
+                .map_err(|error| zkVMError::ProvingFailed(error.to_string()))?;
+            return Ok(GeneratedProof {
+                public_values: proof[..32].to_vec(),
+                proof,
+            });
         }
         if let Self::Verifier { proof_type, .. } = self {
-            anyhow::bail!("prove not supported for verifier-only zkvm {proof_type}");
+            return Err(zkVMError::ProvingFailed(format!(
+                "prove not supported for verifier-only zkvm {proof_type}"
+            )));
         }
 
-        let el_kind = self.proof_type().el_kind();
-        let input = new_payload_request_with_witness.to_zkvm_input(el_kind)?;
         match self {
-            Self::Ere { client, .. } => {
-                let (_, proof, _) = client.prove(input).await?;
-                Ok(proof.0)
+            Self::Ere { servers, .. } => {
+                self.prove_on(&servers.active(), new_payload_request_with_witness)
+                    .await
             }
             Self::Mock { .. } | Self::Verifier { .. } => unreachable!(),
         }
     }
 
+    /// Proves and verifies the payload on the standby ere-server, for shadow proving, returning
+    /// the public values of the verified proof. `None` without a standby.
+    pub(crate) async fn shadow_prove(
+        &self,
+        new_payload_request_with_witness: &NewPayloadRequestWithWitness,
+    ) -> Option<Result<Vec<u8>, zkVMError>> {
+        let Self::Ere { servers, .. } = self else {
+            return None;
+        };
+        let standby = servers.standby()?;
+        let result = async {
+            let generated = self
+                .prove_on(&standby, new_payload_request_with_witness)
+                .await?;
+            let public_values = standby
+                .client
+                .verify(EncodedProof(generated.proof))
+                .await
+                .map_err(|error| zkVMError::VerificationFailed(error.to_string()))?;
+            Ok(public_values.to_vec())
+        };
+        Some(result.await)
+    }
+
+    /// Generates a proof for the given payload on `server`.
+    async fn prove_on(
+        &self,
+        server: &EreServer,
+        new_payload_request_with_witness: &NewPayloadRequestWithWitness,
+    ) -> Result<GeneratedProof, zkVMError> {
+        let el_kind = self.proof_type().el_kind();
+        let input = new_payload_request_with_witness
+            .to_zkvm_input(el_kind)
+            .map_err(|error| zkVMError::InvalidInput(format!("{error:#}")))?;
+        let (public_values, proof, _) = server
+            .client
+            .prove(input)
+            .await
+            .map_err(|error| zkVMError::from_ere_error(error.into()))?;
+        Ok(GeneratedProof {
+            proof: proof.0,
+            public_values: public_values.to_vec(),
+        })
+    }
+
+    /// Executes the `canary` block on `server` and checks that the program validates it, so an
+    /// ere-server running no, a broken, or another EL's program is caught before serving.
+    pub(crate) async fn execute_canary(
+        &self,
+        server: &EreServer,
+        canary: &NewPayloadRequestWithWitness,
+    ) -> Result<(), zkVMError> {
+        let input = canary
+            .to_zkvm_input(self.proof_type().el_kind())
+            .map_err(|error| zkVMError::InvalidInput(format!("{error:#}")))?;
+        let (public_values, _) = server
+            .client
+            .execute(input)
+            .await
+            .map_err(|error| zkVMError::from_ere_error(error.into()))?;
+        check_public_values(&public_values, canary.root())
+    }
+
     /// Verifies a compressed proof against the expected public values.
     pub(crate) async fn verify(
         &self,
@@ -163,7 +418,9 @@ impl zkVMInstance {
         proof: Vec<u8>,
     ) -> Result<(), zkVMError> {
         let public_values: PublicValues = match self {
-            Self::Ere { client, .. } => client
+            Self::Ere { servers, .. } => servers
+                .active()
+                .client
                 .verify(EncodedProof(proof))
                 .await
                 .map_err(|error| zkVMError::VerificationFailed(error.to_string())),
@@ -176,19 +433,7 @@ impl zkVMInstance {
                 .map_err(|error| zkVMError::VerificationFailed(error.to_string())),
         }?;
 
-        let expected = expected_public_values(new_payload_request_root)
-            .map_err(|error| zkVMError::VerificationFailed(error.to_string()))?;
-
-        // For zkVM with fixed size public values, ensure all padding are zeros.
-        if public_values.len() >= 32
-            && public_values[..32] == expected
-            && public_values[32..].iter().all(|byte| *byte == 0)
-        {
-            Ok(())
-        } else {
-            warn!(?public_values, ?expected, "unexpected public values");
-            Err(zkVMError::PublicValuesMismatch)
-        }
+        check_public_values(&public_values, new_payload_request_root)
     }
 
     /// Returns the proof type identifier for this instance.
@@ -210,6 +455,68 @@ impl zkVMInstance {
         }
     }
 
+    /// Returns the minimum available host memory in MiB required to start a proof, if any.
+    pub(crate) fn min_available_memory_mb(&self) -> Option<u64> {
+        match self {
+            Self::Ere {
+                min_available_memory_mb,
+                ..
+            } => *min_available_memory_mb,
+            Self::Mock { .. } | Self::Verifier { .. } => None,
+        }
+    }
+
+    /// Returns the minimum free GPU memory in MiB required to start a proof, if any.
+    pub(crate) fn min_available_gpu_memory_mb(&self) -> Option<u64> {
+        match self {
+            Self::Ere {
+                min_available_gpu_memory_mb,
+                ..
+            } => *min_available_gpu_memory_mb,
+            Self::Mock { .. } | Self::Verifier { .. } => None,
+        }
+    }
+
+    /// Returns the number of retries of proofs failed with an unreachable backend.
+    pub(crate) fn prove_retries(&self) -> u32 {
+        match self {
+            Self::Ere { prove_retries, .. } => *prove_retries,
+            Self::Mock { .. } | Self::Verifier { .. } => 0,
+        }
+    }
+
+    /// Returns whether generated proofs are verified before they are published.
+    pub(crate) fn self_verify(&self) -> bool {
+        match self {
+            Self::Ere { self_verify, .. } => *self_verify,
+            Self::Mock { .. } | Self::Verifier { .. } => false,
+        }
+    }
+
+    /// Returns the percentage of proofs additionally proven on the standby ere-server.
+    pub(crate) fn shadow_percent(&self) -> u8 {
+        match self {
+            Self::Ere { shadow_percent, .. } => *shadow_percent,
+            Self::Mock { .. } | Self::Verifier { .. } => 0,
+        }
+    }
+
+    /// Returns the maximum age of a block when proving starts, if any.
+    pub(crate) fn max_block_age(&self) -> Option<Duration> {
+        match self {
+            Self::Ere { max_block_age, .. } | Self::Mock { max_block_age, .. } => *max_block_age,
+            Self::Verifier { .. } => None,
+        }
+    }
+
+    /// Returns the host resource proofs run on, mock backends use no shared resource.
+    pub(crate) fn resource(&self) -> ProverResource {
+        match self {
+            Self::Ere { resource, .. } => *resource,
+            Self::Mock { .. } | Self::Verifier { .. } => ProverResource::Cpu,
+        }
+    }
+
     /// Returns the backend kind and capabilities for this instance.
     ///
     /// - `Ere`: can prove and verify (remote prover)
@@ -222,6 +529,60 @@ impl zkVMInstance {
             Self::Verifier { .. } => (zkboost_types::BackendKind::Verifier, false, true),
         }
     }
+
+    /// Returns the prover stack information reported by `GET /v1/info`.
+    pub(crate) fn backend_info(&self) -> BackendInfo {
+        let (kind, _, _) = self.backend_capabilities();
+        let (ere_version, image, program_vk_sha256, program_vk_url) = match self {
+            Self::Ere { servers, .. } => (
+                Some(ERE_VERSION),
+                servers.active().image.clone(),
+                None,
+                None,
+            ),
+            Self::Mock { .. } => (None, None, None, None),
+            Self::Verifier {
+                program_vk_sha256,
+                program_vk_url,
+                ..
+            } => (
+                Some(ERE_VERSION),
+                None,
+                Some(*program_vk_sha256),
+                Some(program_vk_url.clone()),
+            ),
+        };
+        let ere_guests_source = ere_version.map(|_| {
+            ERE_GUESTS_SOURCE
+                .strip_prefix("git+")
+                .unwrap_or(ERE_GUESTS_SOURCE)
+                .to_string()
+        });
+        BackendInfo {
+            proof_type: self.proof_type(),
+            kind,
+            ere_version: ere_version.map(str::to_string),
+            image,
+            program_vk_sha256,
+            ere_guests_source,
+            program_vk_url,
+        }
+    }
+}
+
+/// Checks that `public_values` commit to a successful validation of the `NewPayloadRequest` with
+/// `new_payload_request_root`.
+fn check_public_values(
+    public_values: &[u8],
+    new_payload_request_root: Hash256,
+) -> Result<(), zkVMError> {
+    let output = StatelessValidatorOutput::decode(public_values, new_payload_request_root);
+    if output.is_some_and(|output| output.successful_validation) {
+        Ok(())
+    } else {
+        warn!(?public_values, ?output, "unexpected public values");
+        Err(zkVMError::PublicValuesMismatch)
+    }
 }
 
 /// Mock zkVM for testing.
@@ -324,15 +685,6 @@ fn execute(el_kind: ElKind, input: &StatelessInput) -> anyhow::Result<([u8; 32],
     Ok((public_values, input.block.header.gas_used))
 }
 
-/// Computes the expected public values hash for a given payload root.
-pub(crate) fn expected_public_values(
-    new_payload_request_root: Hash256,
-) -> anyhow::Result<[u8; 32]> {
-    let output = StatelessValidatorOutput::new(new_payload_request_root.0, true);
-    let serialized = output.encode_to_vec()?;
-    Ok(Sha256::digest(serialized).into())
-}
-
 #[cfg(test)]
 mod tests {
     use std::sync::Arc;
@@ -344,12 +696,19 @@ mod tests {
 
     /// Creates a test Ere instance with dummy client.
     fn test_ere_instance() -> zkVMInstance {
-        let endpoint = Url::parse("http://localhost:9999").unwrap();
-        let client = zkVMClient::new(endpoint, reqwest::Client::new(), vec![]).unwrap();
+        let active =
+            EreServer::new("http://localhost:9999", None, &reqwest::Client::new()).unwrap();
         zkVMInstance::Ere {
             proof_type: ProofType::RethZisk,
             proof_timeout: Duration::from_secs(10),
-            client: Arc::new(client),
+            servers: Arc::new(EreServers::new(active, None)),
+            shadow_percent: 0,
+            min_available_memory_mb: None,
+            min_available_gpu_memory_mb: None,
+            prove_retries: 0,
+            self_verify: false,
+            max_block_age: None,
+            resource: ProverResource::Cpu,
         }
     }
 
@@ -364,6 +723,7 @@ mod tests {
                 64,
                 false,
             ),
+            max_block_age: None,
         }
     }
 
@@ -372,9 +732,61 @@ mod tests {
         zkVMInstance::Verifier {
             proof_type: ProofType::RethZisk,
             verifier: Arc::new(Verifier::new(zkVMKind::Zisk, &[0; 32]).unwrap()),
+            program_vk_sha256: Hash256::ZERO,
+            program_vk_url: "test.vk".to_string(),
         }
     }
 
+    #[test]
+    fn test_prove_error_failure_reason() {
+        assert_eq!(
+            zkVMError::InvalidInput("bad witness".to_string()).failure_reason(),
+            FailureReason::InvalidInput
+        );
+        assert_eq!(
+            zkVMError::from_ere_error(anyhow::anyhow!("guest panicked")).failure_reason(),
+            FailureReason::ProvingError
+        );
+    }
+
+    #[test]
+    fn test_ere_servers_switch() {
+        let http_client = reqwest::Client::new();
+        let server = |port: u16, image: &str| {
+            EreServer::new(
+                &format!("http://localhost:{port}"),
+                Some(image.to_string()),
+                &http_client,
+            )
+            .unwrap()
+        };
+        let servers = EreServers::new(server(3000, "v1"), Some(server(3001, "v2")));
+
+        assert_eq!(servers.switch().unwrap().image.as_deref(), Some("v2"));
+        assert_eq!(servers.active().endpoint.port(), Some(3001));
+        assert_eq!(servers.standby().unwrap().image.as_deref(), Some("v1"));
+        assert_eq!(servers.switch().unwrap().image.as_deref(), Some("v1"));
+
+        let servers = EreServers::new(server(3000, "v1"), None);
+        assert!(servers.switch().is_none());
+        assert_eq!(servers.active().image.as_deref(), Some("v1"));
+    }
+
+    #[test]
+    fn test_ere_servers_snapshot_ignores_switchover() {
+        let http_client = reqwest::Client::new();
+        let server = |port: u16| {
+            EreServer::new(&format!("http://localhost:{port}"), None, &http_client).unwrap()
+        };
+        let servers = EreServers::new(server(3000), Some(server(3001)));
+
+        let snapshot = servers.snapshot();
+        servers.switch();
+
+        assert_eq!(snapshot.active().endpoint.port(), Some(3000));
+        assert_eq!(servers.active().endpoint.port(), Some(3001));
+    }
+
     #[test]
     fn test_ere_backend_capabilities() {
         let instance = test_ere_instance();
@@ -0,0 +1,275 @@
+//! Proof request recorder, writing accepted proof requests to disk so they can be replayed against
+//! another server version with `zkboost-cli replay`.
+//!
+//! Only the SSZ-encoded `NewPayloadRequest` and the requested proof types are stored, no request
+//! headers or peer information.
+
+use std::{
+    io,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use bytes::Bytes;
+use tokio::{fs, sync::Mutex, task::JoinHandle};
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+use zkboost_types::{Hash256, ProofType, RecordedProofRequest};
+
+use crate::{
+    config::RecordRetentionConfig,
+    metrics::{record_recording_written, record_recordings_pruned, record_recordings_usage},
+    results::now_ms,
+};
+
+/// Interval between two runs of the recording garbage collection.
+const GC_INTERVAL: Duration = Duration::from_secs(600);
+
+/// Writes accepted proof requests into a directory.
+#[derive(Debug)]
+pub(crate) struct Recorder {
+    dir: PathBuf,
+    /// Serializes the metadata read-modify-write of [`Recorder::record`].
+    lock: Mutex<()>,
+}
+
+impl Recorder {
+    /// Creates a recorder writing into `dir`, creating the directory if it does not exist.
+    pub(crate) async fn new(dir: PathBuf) -> io::Result<Self> {
+        fs::create_dir_all(&dir).await?;
+        Ok(Self {
+            dir,
+            lock: Mutex::new(()),
+        })
+    }
+
+    /// Records the SSZ-encoded request body and its metadata.
+    ///
+    /// A request for an already recorded root adds its proof types to the existing metadata,
+    /// keeping the time the root was first received.
+    pub(crate) async fn record(
+        &self,
+        new_payload_request_root: Hash256,
+        proof_types: &[ProofType],
+        body: &Bytes,
+    ) -> io::Result<()> {
+        let _guard = self.lock.lock().await;
+        let start = Instant::now();
+        let metadata_path = self.dir.join(format!("{new_payload_request_root}.json"));
+        let mut record = match fs::read(&metadata_path).await {
+            Ok(existing) => serde_json::from_slice(&existing)?,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => RecordedProofRequest {
+                new_payload_request_root,
+                proof_types: Vec::new(),
+                received_at_ms: now_ms(),
+            },
+            Err(error) => return Err(error),
+        };
+        for proof_type in proof_types {
+            if !record.proof_types.contains(proof_type) {
+                record.proof_types.push(*proof_type);
+            }
+        }
+        let record = serde_json::to_vec_pretty(&record)?;
+        fs::write(
+            self.dir.join(format!("{new_payload_request_root}.ssz")),
+            body,
+        )
+        .await?;
+        fs::write(&metadata_path, &record).await?;
+        record_recording_written((body.len() + record.len()) as u64, start.elapsed());
+        Ok(())
+    }
+
+    /// Spawns a task deleting recordings beyond `retention` and measuring the disk usage of the
+    /// remaining ones every [`GC_INTERVAL`] until shutdown.
+    pub(crate) fn spawn_gc(
+        &self,
+        retention: RecordRetentionConfig,
+        shutdown: CancellationToken,
+    ) -> JoinHandle<()> {
+        let dir = self.dir.clone();
+        tokio::spawn(async move {
+            loop {
+                if retention.is_enabled() {
+                    match prune(&dir, &retention).await {
+                        Ok((0, _)) => {}
+                        Ok((count, bytes)) => {
+                            info!(count, bytes, "pruned recorded proof requests");
+                            record_recordings_pruned(count, bytes);
+                        }
+                        Err(error) => warn!(%error, "pruning recorded proof requests failed"),
+                    }
+                }
+                match usage(&dir).await {
+                    Ok((count, bytes)) => record_recordings_usage(count, bytes),
+                    Err(error) => warn!(%error, "measuring recorded proof requests failed"),
+                }
+                tokio::select! {
+                    _ = shutdown.cancelled() => break,
+                    _ = tokio::time::sleep(GC_INTERVAL) => {}
+                }
+            }
+        })
+    }
+}
+
+/// Deletes the recordings in `dir` beyond `retention`, returning their count and size in bytes.
+async fn prune(dir: &Path, retention: &RecordRetentionConfig) -> io::Result<(usize, u64)> {
+    let mut records = Vec::new();
+    let mut entries = fs::read_dir(dir).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        let path = entry.path();
+        if path.extension().is_none_or(|extension| extension != "json") {
+            continue;
+        }
+        match serde_json::from_slice::<RecordedProofRequest>(&fs::read(&path).await?) {
+            Ok(record) => records.push(record),
+            Err(error) => warn!(path = %path.display(), %error, "skipping unreadable recording"),
+        }
+    }
+
+    let (mut count, mut bytes) = (0, 0);
+    for root in expired(records, retention, now_ms()) {
+        for extension in ["ssz", "json"] {
+            let path = dir.join(format!("{root}.{extension}"));
+            if let Ok(metadata) = fs::metadata(&path).await {
+                fs::remove_file(&path).await?;
+                bytes += metadata.len();
+            }
+        }
+        count += 1;
+    }
+    Ok((count, bytes))
+}
+
+/// Returns the number of recordings in `dir` and their total size in bytes.
+async fn usage(dir: &Path) -> io::Result<(usize, u64)> {
+    let (mut count, mut bytes) = (0, 0);
+    let mut entries = fs::read_dir(dir).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        let path = entry.path();
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("json") => count += 1,
+            Some("ssz") => {}
+            _ => continue,
+        }
+        bytes += entry.metadata().await?.len();
+    }
+    Ok((count, bytes))
+}
+
+/// Returns the roots of the records beyond `retention` at `now_ms`.
+fn expired(
+    mut records: Vec<RecordedProofRequest>,
+    retention: &RecordRetentionConfig,
+    now_ms: u64,
+) -> Vec<Hash256> {
+    // Newest first, so everything past `max_count` is the oldest.
+    records.sort_by_key(|record| std::cmp::Reverse(record.received_at_ms));
+    let max_age_ms = retention
+        .max_age_hours
+        .map(|hours| Duration::from_secs(hours * 3600).as_millis() as u64);
+    records
+        .iter()
+        .enumerate()
+        .filter(|(index, record)| {
+            retention
+                .max_count
+                .is_some_and(|max_count| *index >= max_count)
+                || max_age_ms.is_some_and(|max_age_ms| {
+                    now_ms.saturating_sub(record.received_at_ms) > max_age_ms
+                })
+        })
+        .map(|(_, record)| record.new_payload_request_root)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use bytes::Bytes;
+    use tokio::fs;
+    use zkboost_types::{Hash256, ProofType, RecordedProofRequest};
+
+    use super::{Recorder, expired};
+    use crate::config::RecordRetentionConfig;
+
+    fn record(byte: u8, received_at_ms: u64) -> RecordedProofRequest {
+        RecordedProofRequest {
+            new_payload_request_root: Hash256::repeat_byte(byte),
+            proof_types: vec![ProofType::RethZisk],
+            received_at_ms,
+        }
+    }
+
+    #[test]
+    fn test_expired() {
+        let hour_ms = 3_600_000;
+        let now_ms = 10 * hour_ms;
+        let records = vec![
+            record(1, now_ms - 5 * hour_ms),
+            record(2, now_ms - hour_ms),
+            record(3, now_ms),
+        ];
+
+        let retention = RecordRetentionConfig::default();
+        assert!(expired(records.clone(), &retention, now_ms).is_empty());
+
+        let retention = RecordRetentionConfig {
+            max_age_hours: Some(2),
+            max_count: None,
+        };
+        assert_eq!(
+            expired(records.clone(), &retention, now_ms),
+            [Hash256::repeat_byte(1)]
+        );
+
+        let retention = RecordRetentionConfig {
+            max_age_hours: None,
+            max_count: Some(1),
+        };
+        assert_eq!(
+            expired(records, &retention, now_ms),
+            [Hash256::repeat_byte(2), Hash256::repeat_byte(1)]
+        );
+    }
+
+    async fn read_record(path: &Path) -> RecordedProofRequest {
+        serde_json::from_slice(&fs::read(path).await.unwrap()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_record_merges_proof_types() {
+        let dir = tempfile::tempdir().unwrap();
+        let recorder = Recorder::new(dir.path().join("records")).await.unwrap();
+        let root = Hash256::repeat_byte(1);
+        let body = Bytes::from_static(&[1, 2, 3]);
+
+        recorder
+            .record(root, &[ProofType::RethZisk], &body)
+            .await
+            .unwrap();
+        let first = read_record(&dir.path().join(format!("records/{root}.json"))).await;
+        assert_eq!(first.new_payload_request_root, root);
+        assert_eq!(first.proof_types, [ProofType::RethZisk]);
+
+        recorder
+            .record(root, &[ProofType::RethSP1, ProofType::RethZisk], &body)
+            .await
+            .unwrap();
+        let merged = read_record(&dir.path().join(format!("records/{root}.json"))).await;
+        assert_eq!(
+            merged.proof_types,
+            [ProofType::RethZisk, ProofType::RethSP1]
+        );
+        assert_eq!(merged.received_at_ms, first.received_at_ms);
+        assert_eq!(
+            fs::read(dir.path().join(format!("records/{root}.ssz")))
+                .await
+                .unwrap(),
+            body
+        );
+    }
+}
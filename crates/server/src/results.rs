@@ -0,0 +1,108 @@
+//! Proof results sink, appending one JSON line per proof attempt so proving time and proof size can
+//! be compared across runs with `zkboost-cli report`.
+
+use std::{
+    fs::{File, OpenOptions},
+    io::{self, Write},
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use zkboost_types::ProofResultRecord;
+
+/// Appends [`ProofResultRecord`]s to a JSONL file.
+#[derive(Debug)]
+pub(crate) struct ResultsSink {
+    file: File,
+    label: Option<String>,
+}
+
+impl ResultsSink {
+    /// Opens `path` for appending, creating the file and its parent directory if needed.
+    pub(crate) fn new(path: &Path, label: Option<String>) -> io::Result<Self> {
+        if let Some(parent) = path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            std::fs::create_dir_all(parent)?;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self { file, label })
+    }
+
+    /// Appends a single record as one line, stamped with the configured run label.
+    ///
+    /// Records are small and written with a single `write_all`, so this does not stall the proof
+    /// service loop noticeably.
+    pub(crate) fn append(&mut self, mut record: ProofResultRecord) -> io::Result<()> {
+        record.label = self.label.clone();
+        let mut line = serde_json::to_vec(&record)?;
+        line.push(b'\n');
+        self.file.write_all(&line)
+    }
+}
+
+/// Returns the current Unix timestamp in milliseconds.
+pub(crate) fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use zkboost_types::{FailureReason, Hash256, ProofResultRecord, ProofType};
+
+    use super::ResultsSink;
+
+    fn record(block_number: u64, failure_reason: Option<FailureReason>) -> ProofResultRecord {
+        ProofResultRecord {
+            label: None,
+            server_version: "0.1.0".to_string(),
+            new_payload_request_root: Hash256::repeat_byte(1),
+            block_hash: Hash256::repeat_byte(2),
+            block_number,
+            gas_used: 21_000,
+            proof_type: ProofType::RethZisk,
+            failure_reason,
+            proving_time_ms: 6_000,
+            proof_size: if failure_reason.is_some() { 0 } else { 1_024 },
+            completed_at_ms: 1_700_000_000_000,
+            retries: 1,
+            self_verify_failures: 0,
+        }
+    }
+
+    #[test]
+    fn test_append_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested/results.jsonl");
+        let records = [
+            record(1, None),
+            record(2, Some(FailureReason::ProvingTimeout)),
+            record(3, None),
+        ];
+
+        let mut sink = ResultsSink::new(&path, Some("run-a".to_string())).unwrap();
+        sink.append(records[0].clone()).unwrap();
+        sink.append(records[1].clone()).unwrap();
+        drop(sink);
+        // Reopening appends instead of truncating.
+        let mut sink = ResultsSink::new(&path, None).unwrap();
+        sink.append(records[2].clone()).unwrap();
+
+        let read = std::fs::read_to_string(&path)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str::<ProofResultRecord>(line).unwrap())
+            .collect::<Vec<_>>();
+        let expected = records.map(|mut record| {
+            if record.block_number < 3 {
+                record.label = Some("run-a".to_string());
+            }
+            record
+        });
+        assert_eq!(read, expected);
+    }
+}
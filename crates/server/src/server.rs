@@ -14,48 +14,90 @@ use std::{
 };
 
 use alloy_genesis::ChainConfig;
+use anyhow::{Context, ensure};
+use axum::Router;
 use lru::LruCache;
 use metrics_exporter_prometheus::PrometheusHandle;
 use tokio::{
     net::TcpListener,
-    sync::{RwLock, broadcast, mpsc},
+    sync::{RwLock, Semaphore, broadcast, mpsc},
     task::JoinHandle,
-    time::sleep,
+    time::{Instant, sleep},
 };
 use tokio_util::sync::CancellationToken;
 use tracing::{error, info, warn};
 use zkboost_types::ProofType;
 
 use crate::{
-    config::Config,
+    config::{Config, ProverResource, zkVMConfig},
     dashboard::{DashboardService, DashboardState},
     el_client::ElClient,
-    http::{AppState, router},
-    metrics::{set_build_info, set_programs_loaded},
-    proof::{ProofService, worker, zkvm::zkVMInstance},
+    hardware,
+    health::{check_programs, is_standby_check},
+    http::{AppState, cors_layer, router, routers},
+    metrics::{set_backend_info, set_build_info, set_programs_loaded},
+    proof::{
+        ProofService, input::NewPayloadRequestWithWitness, queue::QueueTracker, worker,
+        zkvm::zkVMInstance,
+    },
+    recorder::Recorder,
+    results::ResultsSink,
+    shutdown,
+    signing::InfoSigner,
     witness::WitnessService,
 };
 
 const CHANNEL_CAPACITY: usize = 128;
 
+/// Backoff before the first retry of failed program canary checks, doubled on every retry.
+const PROGRAM_CHECK_BACKOFF: Duration = Duration::from_secs(2);
+
+/// Upper bound of the backoff between program canary checks.
+const MAX_PROGRAM_CHECK_BACKOFF: Duration = Duration::from_secs(60);
+
 /// Configured server ready to run.
 #[allow(non_camel_case_types, missing_debug_implementations)]
 pub struct zkBoostServer {
     el_client: Arc<ElClient>,
+    http_client: reqwest::Client,
     chain_config: Arc<ChainConfig>,
     zkvms: Arc<HashMap<ProofType, zkVMInstance>>,
     config: Config,
     metrics: PrometheusHandle,
+    info_signer: Option<InfoSigner>,
 }
 
 impl zkBoostServer {
     /// Creates a new server by initialising the EL client, fetching chain config,
     /// and creating zkVM instances from the given configuration.
     pub async fn new(config: Config, metrics: PrometheusHandle) -> anyhow::Result<Self> {
+        check_hardware(&config)?;
+        let info_signer = config
+            .info_signing_key_path
+            .as_deref()
+            .map(InfoSigner::load)
+            .transpose()?;
         info!(url = %config.el_endpoint, "el endpoint configured");
-        let el_client = Arc::new(ElClient::new(config.el_endpoint.clone()));
+        let http_client = config
+            .outbound_http
+            .build_client()
+            .context("failed to build outbound HTTP client")?;
+        if let Some(proxy) = &config.outbound_http.proxy {
+            info!(%proxy, "outbound http proxy configured");
+        }
+        let mut el_client = ElClient::new(config.el_endpoint.clone(), http_client.clone());
+        if let Some(auth) = &config.el_auth {
+            el_client = el_client
+                .with_auth(auth)
+                .context("failed to configure el_auth")?;
+        }
+        let el_client = Arc::new(el_client);
 
-        let chain_config = if let Some(path) = &config.chain_config_path {
+        let chain_config = if config.read_only {
+            // No proofs are generated, so the chain config is never used.
+            info!("read-only mode: proof requests will be rejected");
+            ChainConfig::default()
+        } else if let Some(path) = &config.chain_config_path {
             let content = fs::read_to_string(path)?;
             let chain_config: ChainConfig = serde_json::from_str(&content)?;
             info!("chain config loaded from file");
@@ -78,7 +120,7 @@ impl zkBoostServer {
 
         let mut zkvms = HashMap::new();
         for zkvm_config in &config.zkvm {
-            let instance = zkVMInstance::new(zkvm_config).await?;
+            let instance = zkVMInstance::new(zkvm_config, &http_client).await?;
             let mode = match zkvm_config {
                 crate::config::zkVMConfig::Ere { .. } => "prover",
                 crate::config::zkVMConfig::Mock { .. } => "mock",
@@ -97,15 +139,33 @@ impl zkBoostServer {
             }
             zkvms.insert(zkvm_config.proof_type(), instance);
         }
+        // A read-only server proves nothing, so its programs are not checked.
+        if !config.read_only {
+            let program_check_timeout = Duration::from_secs(config.program_check_timeout_secs);
+            for proof_type in wait_for_programs(&zkvms, &http_client, program_check_timeout).await {
+                error!(%proof_type, "program canary check failed, proof type not served");
+                zkvms.remove(&proof_type);
+            }
+            ensure!(
+                !zkvms.is_empty(),
+                "no program passed its canary check within {program_check_timeout:?}"
+            );
+            info!("program canary checks finished");
+        }
+        for (proof_type, zkvm) in &zkvms {
+            set_backend_info(*proof_type, zkvm.backend_capabilities().0);
+        }
         set_programs_loaded(zkvms.len());
         set_build_info(env!("CARGO_PKG_VERSION"));
 
         Ok(Self {
             el_client,
+            http_client,
             chain_config,
             zkvms: Arc::new(zkvms),
             config,
             metrics,
+            info_signer,
         })
     }
 
@@ -130,51 +190,92 @@ impl zkBoostServer {
         let (dashboard_event_tx, dashboard_event_rx) = broadcast::channel(CHANNEL_CAPACITY);
 
         let mut handles = Vec::new();
+        // A read-only server rejects proof requests, so it runs neither the witness service, the
+        // proof service nor workers.
+        let read_only = self.config.read_only;
 
-        let witness_service = WitnessService::new(
-            self.el_client,
-            proof_service_tx.clone(),
-            dashboard_service_tx.clone(),
-            witness_timeout,
-            self.config.witness_cache_size,
-        );
-        handles.push(witness_service.spawn(shutdown_token.clone(), witness_service_rx));
+        if !read_only {
+            let witness_service = WitnessService::new(
+                self.el_client.clone(),
+                proof_service_tx.clone(),
+                dashboard_service_tx.clone(),
+                witness_timeout,
+                self.config.witness_cache_size,
+            );
+            handles.push(witness_service.spawn(shutdown_token.clone(), witness_service_rx));
 
-        info!("witness service started");
+            info!("witness service started");
+        }
+
+        let queue = Arc::new(QueueTracker::new(
+            self.zkvms
+                .values()
+                .filter(|zkvm| !read_only && !matches!(zkvm, zkVMInstance::Verifier { .. }))
+                .map(zkVMInstance::proof_type),
+        ));
 
+        // Shared by the workers of all GPU backends, so only one GPU proof runs at a time.
+        let gpu = Arc::new(Semaphore::new(1));
         let mut worker_input_txs = HashMap::new();
+        let mut worker_handles = Vec::new();
         for zkvm in self.zkvms.values() {
             // Verifier-only backends don't prove, so they get no worker. Prove
             // requests for those proof_types are dropped at the dispatch layer.
-            if matches!(zkvm, zkVMInstance::Verifier { .. }) {
+            if read_only || matches!(zkvm, zkVMInstance::Verifier { .. }) {
                 continue;
             }
             let (worker_input_tx, worker_input_rx) = mpsc::channel(CHANNEL_CAPACITY);
             worker_input_txs.insert(zkvm.proof_type(), worker_input_tx);
-            handles.push(tokio::spawn(worker::run_worker(
+            worker_handles.push(tokio::spawn(worker::run_worker(
                 zkvm.clone(),
                 shutdown_token.clone(),
                 worker_input_rx,
                 worker_output_tx.clone(),
                 dashboard_service_tx.clone(),
+                queue.clone(),
+                (zkvm.resource() == ProverResource::Gpu).then(|| gpu.clone()),
             )));
         }
 
-        let proof_service = ProofService::new(
-            self.chain_config,
+        // Workers are awaited by the shutdown report, which needs their running proofs finished.
+        handles.push(shutdown::spawn_report(
+            queue.clone(),
+            worker_handles,
             proof_cache.clone(),
-            proof_event_tx,
-            witness_service_tx,
-            dashboard_service_tx.clone(),
-        );
-        handles.push(tokio::spawn(proof_service.run(
+            self.config.record_dir.clone(),
+            self.config.results_path.clone(),
+            self.config.shutdown_report_path.clone(),
             shutdown_token.clone(),
-            proof_service_rx,
-            worker_output_rx,
-            worker_input_txs,
-        )));
+        ));
 
-        info!("proof service started");
+        if !read_only {
+            let results_sink = match &self.config.results_path {
+                Some(path) => {
+                    let results_sink = ResultsSink::new(path, self.config.results_label.clone())?;
+                    info!(path = %path.display(), "appending proof results");
+                    Some(results_sink)
+                }
+                None => None,
+            };
+
+            let proof_service = ProofService::new(
+                self.chain_config,
+                proof_cache.clone(),
+                proof_event_tx,
+                witness_service_tx,
+                dashboard_service_tx.clone(),
+                results_sink,
+                queue.clone(),
+            );
+            handles.push(tokio::spawn(proof_service.run(
+                shutdown_token.clone(),
+                proof_service_rx,
+                worker_output_rx,
+                worker_input_txs,
+            )));
+
+            info!("proof service started");
+        }
 
         let dashboard = if self.config.dashboard.enabled {
             let dashboard = Arc::new(RwLock::new(DashboardState::new(
@@ -196,7 +297,21 @@ impl zkBoostServer {
             None
         };
 
+        let recorder = match &self.config.record_dir {
+            Some(dir) if !read_only => {
+                let recorder = Recorder::new(dir.clone()).await?;
+                info!(dir = %dir.display(), "recording proof requests");
+                handles.push(
+                    recorder.spawn_gc(self.config.record_retention.clone(), shutdown_token.clone()),
+                );
+                Some(recorder)
+            }
+            _ => None,
+        };
+
         let app_state = Arc::new(AppState::new(
+            self.el_client,
+            self.http_client,
             self.zkvms.clone(),
             proof_cache,
             self.metrics,
@@ -204,20 +319,183 @@ impl zkBoostServer {
             proof_service_tx,
             proof_event_rx,
             dashboard_event_rx,
+            recorder,
+            queue,
+            self.config.timeouts.clone(),
+            self.config.redacted(),
+            self.info_signer,
         ));
         let listener = TcpListener::bind((Ipv4Addr::UNSPECIFIED, self.config.port)).await?;
         let addr = listener.local_addr()?;
-        handles.push(tokio::spawn(async move {
-            if let Err(error) = axum::serve(listener, router(app_state))
-                .with_graceful_shutdown(shutdown_token.cancelled_owned())
-                .await
-            {
-                error!(error = %error, "http server error");
+        let mut app = match self.config.admin_port {
+            Some(admin_port) => {
+                let (api, admin) = routers(app_state);
+                let admin_listener = TcpListener::bind((Ipv4Addr::UNSPECIFIED, admin_port)).await?;
+                handles.push(spawn_http_server(
+                    admin_listener,
+                    admin,
+                    shutdown_token.clone(),
+                ));
+                info!(port = admin_port, "admin http server listening");
+                api
             }
-        }));
+            None => router(app_state),
+        };
+        if let Some(cors) = cors_layer(&self.config.cors) {
+            info!(origins = ?self.config.cors.allowed_origins, "cors enabled");
+            app = app.layer(cors);
+        }
+        handles.push(spawn_http_server(listener, app, shutdown_token));
 
         info!(port = self.config.port, "http server listening");
 
         Ok((addr, handles))
     }
 }
+
+/// Checks the hardware requirements of every ere backend against the host, failing on unmet ones
+/// unless the backend only warns.
+fn check_hardware(config: &Config) -> anyhow::Result<()> {
+    let host = hardware::host();
+    info!(
+        cpu_cores = host.cpu_cores,
+        memory_mb = ?host.memory_mb,
+        gpu_memory_mb = ?host.gpu_memory_mb,
+        "host hardware detected"
+    );
+    for zkvm_config in &config.zkvm {
+        let zkVMConfig::Ere { requirements, .. } = zkvm_config else {
+            continue;
+        };
+        let unmet = hardware::unmet_requirements(host, requirements);
+        if unmet.is_empty() {
+            continue;
+        }
+        let proof_type = zkvm_config.proof_type();
+        let unmet = unmet.join("; ");
+        ensure!(
+            requirements.warn_only,
+            "host does not meet the hardware requirements of {proof_type}: {unmet}"
+        );
+        warn!(%proof_type, %unmet, "host does not meet hardware requirements");
+    }
+    Ok(())
+}
+
+/// Waits until every program passes its canary check, retrying with backoff while ere-servers are
+/// still loading their programs, for at most `deadline`. Returns the proof types whose program
+/// still fails at the deadline. Unhealthy standby ere-servers are only logged, they prove nothing
+/// until switched over to.
+async fn wait_for_programs(
+    zkvms: &HashMap<ProofType, zkVMInstance>,
+    http_client: &reqwest::Client,
+    deadline: Duration,
+) -> Vec<ProofType> {
+    let canary = NewPayloadRequestWithWitness::canary().expect("bundled canary block is valid");
+    let deadline = Instant::now() + deadline;
+    let mut backoff = PROGRAM_CHECK_BACKOFF;
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        let (standby, active): (Vec<_>, Vec<_>) =
+            check_programs(zkvms, http_client, &canary, remaining)
+                .await
+                .into_iter()
+                .filter(|(_, check)| !check.healthy)
+                .partition(|(_, check)| is_standby_check(check));
+        for (_, check) in &standby {
+            warn!(check = %check.name, error = ?check.error, "standby program check failed");
+        }
+        if active.is_empty() {
+            return Vec::new();
+        }
+        let failed = active
+            .iter()
+            .map(|(_, check)| format!("{}: {}", check.name, check.error.as_deref().unwrap_or("")))
+            .collect::<Vec<_>>()
+            .join("; ");
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            error!(%failed, "program canary check failed, deadline reached");
+            return active
+                .into_iter()
+                .map(|(proof_type, _)| proof_type)
+                .collect();
+        }
+        warn!(%failed, ?backoff, "program canary check failed, retrying");
+        sleep(backoff.min(remaining)).await;
+        backoff = (backoff * 2).min(MAX_PROGRAM_CHECK_BACKOFF);
+    }
+}
+
+fn spawn_http_server(
+    listener: TcpListener,
+    app: Router,
+    shutdown_token: CancellationToken,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        if let Err(error) = axum::serve(listener, app)
+            .with_graceful_shutdown(shutdown_token.cancelled_owned())
+            .await
+        {
+            error!(error = %error, "http server error");
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, time::Duration};
+
+    use zkboost_types::ProofType;
+
+    use super::wait_for_programs;
+    use crate::{
+        config::{MockProvingTime, zkVMConfig},
+        proof::zkvm::zkVMInstance,
+    };
+
+    #[tokio::test]
+    async fn test_wait_for_programs_gives_up_at_deadline() {
+        let http_client = reqwest::Client::new();
+        let mut zkvms = HashMap::new();
+        for (proof_type, config) in [
+            (
+                ProofType::RethZisk,
+                zkVMConfig::Ere {
+                    proof_type: ProofType::RethZisk,
+                    proof_timeout_secs: 12,
+                    endpoint: "http://127.0.0.1:1".to_string(),
+                    image: None,
+                    standby_endpoint: None,
+                    standby_image: None,
+                    shadow_percent: 0,
+                    min_available_memory_mb: None,
+                    min_available_gpu_memory_mb: None,
+                    prove_retries: 0,
+                    self_verify: false,
+                    requirements: Default::default(),
+                    max_block_age_secs: None,
+                    resource: Default::default(),
+                },
+            ),
+            (
+                ProofType::RethSP1,
+                zkVMConfig::Mock {
+                    proof_type: ProofType::RethSP1,
+                    proof_timeout_secs: 12,
+                    mock_proving_time: MockProvingTime::Constant { ms: 0 },
+                    mock_proof_size: 1024,
+                    mock_failure: false,
+                    max_block_age_secs: None,
+                },
+            ),
+        ] {
+            let zkvm = zkVMInstance::new(&config, &http_client).await.unwrap();
+            zkvms.insert(proof_type, zkvm);
+        }
+
+        let failed = wait_for_programs(&zkvms, &http_client, Duration::from_secs(1)).await;
+
+        assert_eq!(failed, [ProofType::RethZisk]);
+    }
+}
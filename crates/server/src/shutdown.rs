@@ -0,0 +1,205 @@
+//! Shutdown report, telling operators which proofs were finished and which were lost on graceful
+//! shutdown, and where state that survives a restart lives.
+
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use bytes::Bytes;
+use lru::LruCache;
+use serde::Serialize;
+use tokio::{sync::RwLock, task::JoinHandle};
+use tokio_util::sync::CancellationToken;
+use tracing::{error, info, warn};
+use zkboost_types::{Hash256, ProofType, QueueResponse};
+
+use crate::{proof::queue::QueueTracker, results::now_ms};
+
+/// Report of a graceful shutdown, logged and optionally written to `shutdown_report_path`.
+#[derive(Debug, Serialize)]
+pub(crate) struct ShutdownReport {
+    /// Unix timestamp in milliseconds when shutdown started.
+    shutdown_at_ms: u64,
+    /// Proofs that were being generated when shutdown started and were finished before exiting.
+    drained: Vec<ReportedProof>,
+    /// Proofs dispatched to a worker but never started, they must be requested again.
+    abandoned: Vec<ReportedProof>,
+    /// Completed proofs that were only held in memory and are lost.
+    cached_proofs: usize,
+    /// Directory of recorded proof requests, abandoned ones can be replayed from it.
+    record_dir: Option<PathBuf>,
+    /// File proof results are appended to.
+    results_path: Option<PathBuf>,
+}
+
+/// A proof listed in the shutdown report.
+#[derive(Debug, PartialEq, Serialize)]
+struct ReportedProof {
+    proof_type: ProofType,
+    new_payload_request_root: Hash256,
+    block_number: u64,
+}
+
+impl ShutdownReport {
+    /// Builds the report from the queues when shutdown started and once the workers stopped.
+    fn new(
+        at_shutdown: &QueueResponse,
+        at_exit: &QueueResponse,
+        cached_proofs: usize,
+        record_dir: Option<PathBuf>,
+        results_path: Option<PathBuf>,
+    ) -> Self {
+        let unfinished = at_exit
+            .queues
+            .iter()
+            .flat_map(|queue| {
+                let roots = queue.queued.iter().chain(&queue.running);
+                roots.map(|proof| (queue.proof_type, proof.new_payload_request_root))
+            })
+            .collect::<HashSet<_>>();
+        let drained = at_shutdown
+            .queues
+            .iter()
+            .filter_map(|queue| {
+                let proof = queue.running.as_ref()?;
+                (!unfinished.contains(&(queue.proof_type, proof.new_payload_request_root)))
+                    .then_some(ReportedProof {
+                        proof_type: queue.proof_type,
+                        new_payload_request_root: proof.new_payload_request_root,
+                        block_number: proof.block_number,
+                    })
+            })
+            .collect();
+        let abandoned = at_exit
+            .queues
+            .iter()
+            .flat_map(|queue| {
+                let proofs = queue.running.iter().chain(&queue.queued);
+                proofs.map(|proof| ReportedProof {
+                    proof_type: queue.proof_type,
+                    new_payload_request_root: proof.new_payload_request_root,
+                    block_number: proof.block_number,
+                })
+            })
+            .collect();
+        Self {
+            shutdown_at_ms: now_ms(),
+            drained,
+            abandoned,
+            cached_proofs,
+            record_dir,
+            results_path,
+        }
+    }
+
+    fn log(&self) {
+        for proof in &self.abandoned {
+            warn!(
+                proof_type = %proof.proof_type,
+                root = %proof.new_payload_request_root,
+                block_number = proof.block_number,
+                "proof abandoned on shutdown"
+            );
+        }
+        info!(
+            drained = self.drained.len(),
+            abandoned = self.abandoned.len(),
+            cached_proofs = self.cached_proofs,
+            record_dir = ?self.record_dir,
+            results_path = ?self.results_path,
+            "shutdown report"
+        );
+    }
+
+    fn write(&self, path: &Path) -> anyhow::Result<()> {
+        std::fs::write(path, serde_json::to_vec_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+/// Spawns a task that waits for shutdown, lets the workers finish their running proofs, and then
+/// logs the shutdown report and writes it to `report_path` if set.
+pub(crate) fn spawn_report(
+    queue: Arc<QueueTracker>,
+    worker_handles: Vec<JoinHandle<()>>,
+    proof_cache: Arc<RwLock<LruCache<(Hash256, ProofType), Bytes>>>,
+    record_dir: Option<PathBuf>,
+    results_path: Option<PathBuf>,
+    report_path: Option<PathBuf>,
+    shutdown: CancellationToken,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        shutdown.cancelled().await;
+        let at_shutdown = queue.to_response();
+        for handle in worker_handles {
+            if let Err(error) = handle.await {
+                error!(error = %error, "worker task failed");
+            }
+        }
+        let cached_proofs = proof_cache.read().await.len();
+        let report = ShutdownReport::new(
+            &at_shutdown,
+            &queue.to_response(),
+            cached_proofs,
+            record_dir,
+            results_path,
+        );
+        report.log();
+        if let Some(path) = report_path {
+            match report.write(&path) {
+                Ok(()) => info!(path = %path.display(), "shutdown report written"),
+                Err(error) => {
+                    error!(path = %path.display(), error = %error, "shutdown report write failed")
+                }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use zkboost_types::{Hash256, ProofType, ProofTypeQueue, QueueResponse, QueuedProof};
+
+    use super::{ReportedProof, ShutdownReport};
+
+    fn queued_proof(byte: u8) -> QueuedProof {
+        QueuedProof {
+            new_payload_request_root: Hash256::repeat_byte(byte),
+            block_number: byte.into(),
+            elapsed_ms: 0,
+        }
+    }
+
+    #[test]
+    fn test_report_drained_and_abandoned() {
+        let at_shutdown = QueueResponse {
+            queues: vec![ProofTypeQueue {
+                proof_type: ProofType::RethZisk,
+                queued: vec![queued_proof(2), queued_proof(3)],
+                running: Some(queued_proof(1)),
+            }],
+        };
+        let at_exit = QueueResponse {
+            queues: vec![ProofTypeQueue {
+                proof_type: ProofType::RethZisk,
+                queued: vec![queued_proof(2), queued_proof(3)],
+                running: None,
+            }],
+        };
+
+        let report = ShutdownReport::new(&at_shutdown, &at_exit, 4, None, None);
+
+        assert_eq!(
+            report.drained,
+            [ReportedProof {
+                proof_type: ProofType::RethZisk,
+                new_payload_request_root: Hash256::repeat_byte(1),
+                block_number: 1,
+            }]
+        );
+        assert_eq!(report.abandoned.len(), 2);
+        assert_eq!(report.cached_proofs, 4);
+    }
+}
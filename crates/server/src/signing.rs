@@ -0,0 +1,59 @@
+//! Signing of `GET /v1/info/signed` responses with the operator's minisign key, so relayers can
+//! attest which server produced proofs in multi-operator setups.
+//!
+//! Signatures can be checked with `minisign -V` or `guest_loader::verify_program_and_signature`
+//! against the operator's public key.
+
+use std::{fmt, path::Path};
+
+use anyhow::{Context, anyhow};
+use minisign::{SecretKey, SecretKeyBox};
+
+/// Minisign secret key signing server info.
+pub(crate) struct InfoSigner {
+    secret_key: SecretKey,
+}
+
+impl fmt::Debug for InfoSigner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InfoSigner").finish_non_exhaustive()
+    }
+}
+
+impl InfoSigner {
+    /// Loads an unencrypted minisign secret key, as created by `minisign -G -W`.
+    pub(crate) fn load(path: &Path) -> anyhow::Result<Self> {
+        let secret_key_box =
+            std::fs::read_to_string(path).with_context(|| format!("read {}", path.display()))?;
+        let secret_key = SecretKeyBox::from_string(&secret_key_box)
+            .and_then(SecretKeyBox::into_unencrypted_secret_key)
+            .map_err(|error| anyhow!("invalid minisign secret key {}: {error}", path.display()))?;
+        Ok(Self { secret_key })
+    }
+
+    /// Returns the minisign signature of `data`.
+    pub(crate) fn sign(&self, data: &[u8]) -> anyhow::Result<String> {
+        let signature = minisign::sign(None, &self.secret_key, data, None, None)
+            .map_err(|error| anyhow!("sign server info: {error}"))?;
+        Ok(signature.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use minisign::{KeyPair, SignatureBox};
+
+    use super::InfoSigner;
+
+    #[test]
+    fn test_sign_verifies() {
+        let KeyPair { pk, sk } = KeyPair::generate_unencrypted_keypair().unwrap();
+        let signer = InfoSigner { secret_key: sk };
+
+        let signature = signer.sign(b"info").unwrap();
+
+        let signature = SignatureBox::from_string(&signature).unwrap();
+        minisign::verify(&pk, &signature, &b"info"[..], true, false, false).unwrap();
+        assert!(minisign::verify(&pk, &signature, &b"other"[..], true, false, false).is_err());
+    }
+}
@@ -22,7 +22,9 @@ use tracing::{Instrument, Span, debug, error, info, info_span, record_all, trace
 use zkboost_types::Hash256;
 
 use crate::{
-    dashboard::DashboardMessage, el_client::ElClient, metrics::record_witness_fetch,
+    dashboard::DashboardMessage,
+    el_client::ElClient,
+    metrics::{record_cache_insert, record_witness_fetch},
     proof::ProofServiceMessage,
 };
 
@@ -113,7 +115,12 @@ impl WitnessService {
         self.requested.remove(&block_hash);
         match witness {
             Some((witness, witness_size)) => {
-                self.witness_cache.put(block_hash, witness.clone());
+                let evicted = self.witness_cache.push(block_hash, witness.clone());
+                record_cache_insert(
+                    "witness",
+                    evicted.is_some_and(|(evicted_hash, _)| evicted_hash != block_hash),
+                    self.witness_cache.len(),
+                );
 
                 info!(%block_hash, "fetched witness");
 
@@ -2,8 +2,9 @@
 
 use std::{
     collections::HashMap,
+    env,
     net::Ipv4Addr,
-    sync::{Arc, OnceLock},
+    sync::{Arc, Mutex, OnceLock},
     time::{Duration, Instant},
 };
 
@@ -14,10 +15,16 @@ use bytes::Bytes;
 use futures::StreamExt;
 use metrics_exporter_prometheus::PrometheusBuilder;
 use stateless::ExecutionWitness;
+use strum::IntoEnumIterator;
 use tokio::net::TcpListener;
-use zkboost_client::{MainnetEthSpec, zkBoostClient};
+use zkboost_client::{
+    HttpOptions, MainnetEthSpec, ProofOutcome, TransferProgress, prove_on_all, zkBoostClient,
+};
 use zkboost_server::{
-    config::{Config, DashboardConfig, zkVMConfig},
+    config::{
+        Config, CorsConfig, DashboardConfig, OutboundHttpConfig, RecordRetentionConfig,
+        TimeoutConfig, zkVMConfig,
+    },
     server::zkBoostServer,
 };
 use zkboost_types::{
@@ -25,6 +32,9 @@ use zkboost_types::{
     ProofStatus, ProofType, TreeHash,
 };
 
+/// SSZ-encoded `NewPayloadRequest` of the fixture block.
+const NEW_PAYLOAD_REQUEST: &[u8] = include_bytes!("fixture/new_payload_request.ssz");
+
 struct Fixture {
     new_payload_request: NewPayloadRequest<MainnetEthSpec>,
     new_payload_request_root: Hash256,
@@ -34,7 +44,6 @@ struct Fixture {
 
 impl Fixture {
     fn load() -> Self {
-        const NEW_PAYLOAD_REQUEST: &[u8] = include_bytes!("fixture/new_payload_request.ssz");
         const CHAIN_CONFIG: &str = include_str!("fixture/chain_config.json");
         const EXECUTION_WITNESS: &str = include_str!("fixture/execution_witness.json");
         let new_payload_request = NewPayloadRequest::from_ssz_bytes(NEW_PAYLOAD_REQUEST).unwrap();
@@ -131,12 +140,27 @@ async fn start_zkboost_server(
 ) -> (url::Url, tokio_util::sync::CancellationToken) {
     let config = Config {
         port: 0,
+        admin_port: None,
         el_endpoint,
+        el_auth: None,
+        outbound_http: OutboundHttpConfig::default(),
         chain_config_path: None,
         witness_timeout_secs,
         proof_cache_size: 128,
         witness_cache_size: 128,
+        record_dir: None,
+        record_retention: RecordRetentionConfig::default(),
+        results_path: None,
+        results_label: None,
+        shutdown_report_path: None,
+        min_free_disk_space_mb: 1024,
+        require_signed_programs: false,
+        program_check_timeout_secs: 600,
+        read_only: false,
+        info_signing_key_path: None,
         dashboard: DashboardConfig::default(),
+        cors: CorsConfig::default(),
+        timeouts: TimeoutConfig::default(),
         zkvm: zkvm_configs,
     };
     let metrics = PrometheusBuilder::new().build_recorder().handle();
@@ -158,32 +182,40 @@ struct Behavior {
 struct TestHarness {
     fixture: Fixture,
     client: zkBoostClient,
-    proof_type: ProofType,
+    proof_types: Vec<ProofType>,
     shutdown: tokio_util::sync::CancellationToken,
 }
 
 impl TestHarness {
     async fn new(behavior: Behavior) -> Self {
+        Self::with_proof_types(behavior, vec![ProofType::EthrexZisk]).await
+    }
+
+    /// Starts a single server with one mock zkVM per proof type, all sharing the same fixture.
+    async fn with_proof_types(behavior: Behavior, proof_types: Vec<ProofType>) -> Self {
         let fixture = Fixture::load();
         let el_endpoint =
             start_mock_el(&fixture, behavior.witness_timeout, behavior.witness_delay).await;
         let witness_timeout_secs = if behavior.witness_timeout { 1 } else { 12 };
         let proof_timeout_secs = if behavior.proof_timeout { 1 } else { 12 };
-        let proof_type = ProofType::EthrexZisk;
-        let zkvm_config = zkVMConfig::Mock {
-            proof_type,
-            proof_timeout_secs,
-            mock_proving_time: zkboost_server::config::MockProvingTime::Constant { ms: 6000 },
-            mock_proof_size: 128 << 10,
-            mock_failure: behavior.proof_failure,
-        };
+        let zkvm_configs = proof_types
+            .iter()
+            .map(|&proof_type| zkVMConfig::Mock {
+                proof_type,
+                proof_timeout_secs,
+                mock_proving_time: zkboost_server::config::MockProvingTime::Constant { ms: 6000 },
+                mock_proof_size: 128 << 10,
+                mock_failure: behavior.proof_failure,
+                max_block_age_secs: None,
+            })
+            .collect();
         let (zkboost_endpoint, shutdown) =
-            start_zkboost_server(el_endpoint, vec![zkvm_config], witness_timeout_secs).await;
+            start_zkboost_server(el_endpoint, zkvm_configs, witness_timeout_secs).await;
         let client = zkBoostClient::new(zkboost_endpoint);
         Self {
             client,
             fixture,
-            proof_type,
+            proof_types,
             shutdown,
         }
     }
@@ -191,7 +223,7 @@ impl TestHarness {
     async fn request_proof(&self) {
         let new_payload_request_root = self
             .client
-            .request_proof(&self.fixture.new_payload_request, &[self.proof_type])
+            .request_proof(&self.fixture.new_payload_request, &self.proof_types)
             .await
             .unwrap()
             .new_payload_request_root;
@@ -202,23 +234,27 @@ impl TestHarness {
         );
     }
 
-    async fn wait_for_event(&self) -> ProofEvent {
+    /// Waits for one proof event per configured proof type.
+    async fn wait_for_events(&self) -> HashMap<ProofType, ProofEvent> {
         let mut stream = Box::pin(
             self.client
                 .subscribe_proof_events(Some(self.fixture.new_payload_request_root)),
         );
-        let proof_event = tokio::time::timeout(Duration::from_secs(30), async {
-            stream.next().await.unwrap().unwrap()
+        let mut proof_events = HashMap::new();
+        tokio::time::timeout(Duration::from_secs(30), async {
+            while proof_events.len() < self.proof_types.len() {
+                let proof_event = stream.next().await.unwrap().unwrap();
+                assert_eq!(
+                    proof_event.new_payload_request_root(),
+                    self.fixture.new_payload_request_root
+                );
+                proof_events.insert(proof_event.proof_type(), proof_event);
+            }
         })
         .await
         .unwrap();
 
-        assert_eq!(
-            proof_event.new_payload_request_root(),
-            self.fixture.new_payload_request_root
-        );
-
-        proof_event
+        proof_events
     }
 
     async fn assert_proof_event(
@@ -226,19 +262,21 @@ impl TestHarness {
         proof_event_kind: ProofEventKind,
         failure_reason: Option<FailureReason>,
     ) {
-        let proof_event = self.wait_for_event().await;
-
-        assert_eq!(proof_event.kind(), proof_event_kind);
-        assert_eq!(proof_event.proof_type(), self.proof_type);
-        assert_eq!(
-            proof_event.new_payload_request_root(),
-            self.fixture.new_payload_request_root
-        );
-        if let Some(failure_reason) = failure_reason {
-            assert!(matches!(
-                proof_event,
-                ProofEvent::ProofFailure(ProofFailure { reason, .. }) if reason == failure_reason
-            ))
+        let proof_events = self.wait_for_events().await;
+
+        for proof_type in &self.proof_types {
+            let proof_event = &proof_events[proof_type];
+            assert_eq!(proof_event.kind(), proof_event_kind);
+            assert_eq!(
+                proof_event.new_payload_request_root(),
+                self.fixture.new_payload_request_root
+            );
+            if let Some(failure_reason) = failure_reason {
+                assert!(matches!(
+                    proof_event,
+                    ProofEvent::ProofFailure(ProofFailure { reason, .. }) if *reason == failure_reason
+                ))
+            }
         }
     }
 
@@ -253,35 +291,60 @@ impl TestHarness {
     }
 
     async fn assert_get_proof_is_valid(&self) {
-        let proof = self
-            .client
-            .get_proof(self.fixture.new_payload_request_root, self.proof_type)
-            .await
-            .unwrap();
-
-        let verification = self
-            .client
-            .verify_proof(
-                self.fixture.new_payload_request_root,
-                self.proof_type,
-                &proof,
-            )
-            .await
-            .unwrap();
-
-        assert_eq!(verification.status, ProofStatus::Valid);
+        for &proof_type in &self.proof_types {
+            let proof = self
+                .client
+                .get_proof(self.fixture.new_payload_request_root, proof_type)
+                .await
+                .unwrap();
+
+            let verification = self
+                .client
+                .verify_proof(self.fixture.new_payload_request_root, proof_type, &proof)
+                .await
+                .unwrap();
+
+            assert_eq!(verification.status, ProofStatus::Valid, "{proof_type}");
+        }
     }
 
     async fn assert_get_proof_not_found(&self) {
-        assert!(matches!(
-            self.client
-                .get_proof(self.fixture.new_payload_request_root, self.proof_type)
-                .await,
-            Err(zkboost_client::Error::NotFound(_))
-        ));
+        for &proof_type in &self.proof_types {
+            assert!(matches!(
+                self.client
+                    .get_proof(self.fixture.new_payload_request_root, proof_type)
+                    .await,
+                Err(zkboost_client::Error::NotFound(_))
+            ));
+        }
     }
 }
 
+/// Proof types for the matrix test, optionally narrowed with the comma-separated
+/// `ZKBOOST_TEST_ELS` (e.g. `reth,ethrex`) and `ZKBOOST_TEST_ZKVMS` (e.g. `sp1,zisk`) environment
+/// variables. Defaults to every proof type.
+fn matrix_proof_types() -> Vec<ProofType> {
+    let filter = |var: &str| {
+        env::var(var).ok().map(|value| {
+            value
+                .split(',')
+                .map(|part| part.trim().to_owned())
+                .collect::<Vec<_>>()
+        })
+    };
+    let els = filter("ZKBOOST_TEST_ELS");
+    let zkvms = filter("ZKBOOST_TEST_ZKVMS");
+    ProofType::iter()
+        .filter(|proof_type| {
+            let (el, zkvm) = proof_type.as_str().split_once('-').unwrap();
+            els.as_ref().is_none_or(|els| els.iter().any(|e| e == el))
+                && zkvms
+                    .as_ref()
+                    .is_none_or(|zkvms| zkvms.iter().any(|z| z == zkvm))
+        })
+        .collect()
+}
+
 impl Drop for TestHarness {
     fn drop(&mut self) {
         self.shutdown.cancel();
@@ -299,6 +362,20 @@ async fn test_proof_complete() {
     harness.assert_proof_complete().await;
 }
 
+#[tokio::test]
+async fn test_proof_complete_matrix() {
+    let proof_types = matrix_proof_types();
+    assert!(
+        !proof_types.is_empty(),
+        "matrix filters match no proof type"
+    );
+    let harness = TestHarness::with_proof_types(Behavior::default(), proof_types).await;
+
+    harness.request_proof().await;
+    harness.assert_proof_complete().await;
+    harness.assert_get_proof_is_valid().await;
+}
+
 #[tokio::test]
 async fn test_proof_complete_with_witness_delay() {
     let behavior = Behavior {
@@ -356,3 +433,221 @@ async fn test_proof_timeout() {
         .await;
     harness.assert_get_proof_not_found().await;
 }
+
+#[tokio::test]
+async fn test_prove_block() {
+    let harness = TestHarness::new(Behavior::default()).await;
+
+    let outcomes = harness
+        .client
+        .prove_block(
+            &harness.fixture.new_payload_request,
+            &harness.proof_types,
+            Duration::from_secs(30),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(outcomes.len(), harness.proof_types.len());
+    for proof_type in &harness.proof_types {
+        let ProofOutcome::Proof(proof) = &outcomes[proof_type] else {
+            panic!("no proof for {proof_type}");
+        };
+        let verification = harness
+            .client
+            .verify_proof(harness.fixture.new_payload_request_root, *proof_type, proof)
+            .await
+            .unwrap();
+        assert_eq!(verification.status, ProofStatus::Valid);
+    }
+
+    // Proofs of the block are cached now and returned without waiting.
+    let outcomes = harness
+        .client
+        .prove_block(
+            &harness.fixture.new_payload_request,
+            &harness.proof_types,
+            Duration::ZERO,
+        )
+        .await
+        .unwrap();
+    assert_eq!(outcomes.len(), harness.proof_types.len());
+}
+
+#[tokio::test]
+async fn test_prove_block_duplicate_proof_types() {
+    let harness = TestHarness::new(Behavior::default()).await;
+    let proof_types = [harness.proof_types.clone(), harness.proof_types.clone()].concat();
+
+    let outcomes = tokio::time::timeout(
+        Duration::from_secs(30),
+        harness.client.prove_block(
+            &harness.fixture.new_payload_request,
+            &proof_types,
+            Duration::from_secs(600),
+        ),
+    )
+    .await
+    .expect("prove_block waited for duplicates")
+    .unwrap();
+
+    assert_eq!(outcomes.len(), harness.proof_types.len());
+    for proof_type in &harness.proof_types {
+        assert!(matches!(outcomes[proof_type], ProofOutcome::Proof(_)));
+    }
+}
+
+#[tokio::test]
+async fn test_prove_block_failure() {
+    let behavior = Behavior {
+        proof_failure: true,
+        ..Default::default()
+    };
+    let harness = TestHarness::new(behavior).await;
+
+    let outcomes = harness
+        .client
+        .prove_block(
+            &harness.fixture.new_payload_request,
+            &harness.proof_types,
+            Duration::from_secs(30),
+        )
+        .await
+        .unwrap();
+
+    for proof_type in &harness.proof_types {
+        assert!(matches!(
+            &outcomes[proof_type],
+            ProofOutcome::Failure(ProofFailure {
+                reason: FailureReason::ProvingError,
+                ..
+            })
+        ));
+    }
+}
+
+#[tokio::test]
+async fn test_request_proof_stream() {
+    let harness = TestHarness::new(Behavior::default()).await;
+
+    let response = harness
+        .client
+        .request_proof_stream(
+            std::io::Cursor::new(NEW_PAYLOAD_REQUEST),
+            &harness.proof_types,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        response.new_payload_request_root,
+        harness.fixture.new_payload_request_root
+    );
+    harness.assert_proof_complete().await;
+    harness.assert_get_proof_is_valid().await;
+}
+
+#[tokio::test]
+async fn test_prove_on_all() {
+    let proving = TestHarness::new(Behavior::default()).await;
+    let failing = TestHarness::new(Behavior {
+        proof_failure: true,
+        ..Default::default()
+    })
+    .await;
+    let clients = [proving.client.clone(), failing.client.clone()];
+
+    let results = prove_on_all(
+        &clients,
+        &proving.fixture.new_payload_request,
+        &proving.proof_types,
+        Duration::from_secs(30),
+    )
+    .collect::<Vec<_>>()
+    .await;
+
+    assert_eq!(results.len(), clients.len());
+    for (client, outcomes) in results {
+        let outcome = &outcomes.unwrap()[&ProofType::EthrexZisk];
+        if client.endpoint() == proving.client.endpoint() {
+            assert!(matches!(outcome, ProofOutcome::Proof(_)));
+        } else {
+            assert_eq!(client.endpoint(), failing.client.endpoint());
+            assert!(matches!(outcome, ProofOutcome::Failure(_)));
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_transfer_progress() {
+    let harness = TestHarness::new(Behavior::default()).await;
+    let total = NEW_PAYLOAD_REQUEST.len() as u64;
+
+    let uploaded = Arc::new(Mutex::new(Vec::new()));
+    harness
+        .client
+        .request_proof_stream_with_progress(
+            std::io::Cursor::new(NEW_PAYLOAD_REQUEST),
+            Some(total),
+            &harness.proof_types,
+            {
+                let uploaded = uploaded.clone();
+                move |progress| uploaded.lock().unwrap().push(progress)
+            },
+        )
+        .await
+        .unwrap();
+    let uploaded = uploaded.lock().unwrap().clone();
+    assert!(uploaded.is_sorted_by_key(|progress| progress.transferred));
+    assert_eq!(
+        uploaded.last(),
+        Some(&TransferProgress {
+            transferred: total,
+            total: Some(total),
+        })
+    );
+
+    harness.assert_proof_complete().await;
+    let mut downloaded = Vec::new();
+    let proof = harness
+        .client
+        .get_proof_with_progress(
+            harness.fixture.new_payload_request_root,
+            ProofType::EthrexZisk,
+            |progress| downloaded.push(progress),
+        )
+        .await
+        .unwrap();
+    assert!(downloaded.is_sorted_by_key(|progress| progress.transferred));
+    assert_eq!(
+        downloaded.last().map(|progress| progress.transferred),
+        Some(proof.len() as u64)
+    );
+}
+
+#[tokio::test]
+async fn test_http2_prior_knowledge() {
+    let harness = TestHarness::new(Behavior::default()).await;
+    let options = HttpOptions {
+        http2_prior_knowledge: true,
+        http2_keep_alive_interval: Some(Duration::from_secs(10)),
+        tcp_keepalive: Some(Duration::from_secs(30)),
+        pool_idle_timeout: Some(Duration::from_secs(60)),
+        pool_max_idle_per_host: Some(1),
+        user_agent: Some("zkboost-test".to_string()),
+    };
+    let client =
+        zkBoostClient::with_http_options(harness.client.endpoint().clone(), &options).unwrap();
+
+    // Concurrent requests share the single h2c connection.
+    let (proof_types, response) = tokio::join!(
+        client.get_proof_types(),
+        client.request_proof(&harness.fixture.new_payload_request, &harness.proof_types),
+    );
+    assert!(!proof_types.unwrap().proof_types.is_empty());
+    assert_eq!(
+        response.unwrap().new_payload_request_root,
+        harness.fixture.new_payload_request_root
+    );
+    harness.assert_proof_complete().await;
+}
@@ -2,6 +2,9 @@
 //!
 //! This crate contains the types used by both the zkboost server and client
 //! for REST API communication and SSE event streaming.
+//!
+//! The `public-values` feature adds `StatelessValidatorOutput` to compute and decode the public
+//! values of proofs. It depends on the guest codec crates, so it is off by default.
 
 #![warn(unused_crate_dependencies)]
 
@@ -14,6 +17,8 @@ use serde::{Deserialize, Serialize};
 
 mod new_payload_request;
 mod proof_type;
+#[cfg(feature = "public-values")]
+mod public_values;
 
 #[rustfmt::skip]
 pub use {
@@ -23,6 +28,16 @@ pub use {
     new_payload_request::*,
     proof_type::*,
 };
+#[cfg(feature = "public-values")]
+pub use public_values::*;
+
+/// Response header of `GET /v1/execution_proofs/{new_payload_request_root}/{proof_type}` holding
+/// the `0x`-prefixed SHA-256 of the whole proof, also on partial (range) responses.
+pub const PROOF_SHA256_HEADER: &str = "x-proof-sha256";
+
+/// Request and response header holding the id of an API request, echoed if the client sent one and
+/// generated otherwise. Error responses also carry it as `request_id`.
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
 
 /// Query params for `POST /v1/execution_proof_requests`.
 #[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
@@ -40,6 +55,21 @@ pub struct ProofRequestQuery {
 pub struct ProofRequestResponse {
     /// The tree-hash root of the `NewPayloadRequest` used as the identifier.
     pub new_payload_request_root: Hash256,
+    /// Queue estimate of every requested proof type, in request order.
+    #[serde(default)]
+    pub queue: Vec<QueueEstimate>,
+}
+
+/// Queue state of a proving backend at the time a proof was requested.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct QueueEstimate {
+    /// The proof type identifier (e.g., "reth-zisk").
+    pub proof_type: ProofType,
+    /// Number of proofs queued or running ahead of the request.
+    pub proofs_ahead: usize,
+    /// Estimated milliseconds until proving of the request starts, from the median of recent
+    /// proving times. `None` until a proof of this type was generated.
+    pub estimated_start_ms: Option<u64>,
 }
 
 /// Query params for `GET /v1/execution_proof_requests` (SSE).
@@ -49,6 +79,14 @@ pub struct ProofEventQuery {
     pub new_payload_request_root: Option<Hash256>,
 }
 
+/// Query params for `GET /v1/execution_proofs/{new_payload_request_root}/{proof_type}`.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ProofQuery {
+    /// Seconds to wait for the proof to complete if it is not available yet, capped by the
+    /// server's request timeout.
+    pub wait_secs: Option<u64>,
+}
+
 /// Query params for `POST /v1/execution_proof_verifications`.
 #[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub struct ProofVerificationQuery {
@@ -63,6 +101,20 @@ pub struct ProofVerificationQuery {
 pub struct ProofVerificationResponse {
     /// The verification result.
     pub status: ProofStatus,
+    /// Why the proof is invalid, `None` if it is valid.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub invalid_reason: Option<InvalidProofReason>,
+}
+
+/// Reason a proof failed verification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InvalidProofReason {
+    /// The proof did not pass verification by the zkVM.
+    VerificationFailed,
+    /// The proof is valid but commits to public values of a different `NewPayloadRequest` than
+    /// the requested root.
+    PublicValuesMismatch,
 }
 
 /// Verification status returned by the proof verification endpoint.
@@ -109,6 +161,223 @@ pub enum BackendKind {
     Verifier,
 }
 
+/// Response for `GET /v1/info`.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ServerInfoResponse {
+    /// Version of the zkboost server.
+    pub version: String,
+    /// Git commit the server was built from, `unknown` if built outside a git checkout.
+    pub git_sha: String,
+    /// Unix timestamp (seconds) of the server build.
+    pub build_timestamp: u64,
+    /// Prover stack of every configured proof type.
+    pub backends: Vec<BackendInfo>,
+    /// Hardware of the host the server runs on.
+    #[serde(default)]
+    pub hardware: Option<HardwareInfo>,
+    /// Nonce supplied by the caller of `GET /v1/info/signed`, proving the signature is fresh.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<String>,
+}
+
+/// Query params for `GET /v1/info/signed`.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SignedServerInfoQuery {
+    /// Nonce included in the signed server info.
+    pub nonce: Option<String>,
+}
+
+/// Response for `GET /v1/info/signed`.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SignedServerInfo {
+    /// JSON-encoded [`ServerInfoResponse`], exactly the bytes that were signed.
+    pub info: String,
+    /// Minisign signature of `info` by the server operator's key.
+    pub signature: String,
+}
+
+impl SignedServerInfo {
+    /// Decodes the signed server info. The signature must be verified separately.
+    pub fn decode_info(&self) -> serde_json::Result<ServerInfoResponse> {
+        serde_json::from_str(&self.info)
+    }
+}
+
+/// Hardware detected on the host of a zkboost server.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct HardwareInfo {
+    /// Number of CPU cores available to the server.
+    pub cpu_cores: usize,
+    /// Installed memory in MiB, `None` if it cannot be read (non-Linux hosts).
+    pub memory_mb: Option<u64>,
+    /// Memory in MiB of every detected NVIDIA GPU.
+    pub gpu_memory_mb: Vec<u64>,
+}
+
+/// Prover stack information of a single configured proof type.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct BackendInfo {
+    /// The proof type identifier (e.g., "reth-zisk").
+    pub proof_type: ProofType,
+    /// The backend kind.
+    pub kind: BackendKind,
+    /// Version of the `ere` crates the backend is driven with, `None` for mock backends.
+    pub ere_version: Option<String>,
+    /// Docker image (tag or digest) of the ere-server, as configured by the operator.
+    pub image: Option<String>,
+    /// SHA-256 of the program verifying key, only known for verifier-only backends.
+    pub program_vk_sha256: Option<Hash256>,
+    /// `eth-act/ere-guests` release (git URL with tag and revision) the guest inputs are encoded
+    /// with, `None` for mock backends.
+    pub ere_guests_source: Option<String>,
+    /// URL or path the program verifying key was loaded from, only set for verifier-only
+    /// backends.
+    pub program_vk_url: Option<String>,
+}
+
+/// Query params for `GET /v1/proof_types/{proof_type}/estimate`.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ProvingTimeEstimateQuery {
+    /// Gas used of the block to estimate for, recent blocks of similar gas used are considered
+    /// only.
+    pub gas_used: Option<u64>,
+}
+
+/// Response for `GET /v1/proof_types/{proof_type}/estimate`.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ProvingTimeEstimate {
+    /// The proof type identifier (e.g., "reth-zisk").
+    pub proof_type: ProofType,
+    /// Number of recent proofs the estimate is based on.
+    pub samples: usize,
+    /// Median proving time in milliseconds, `None` without samples.
+    pub p50_ms: Option<u64>,
+    /// 95th percentile proving time in milliseconds, `None` without samples.
+    pub p95_ms: Option<u64>,
+}
+
+/// Response for `GET /v1/queue`.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct QueueResponse {
+    /// Queue of every proving backend, sorted by proof type.
+    pub queues: Vec<ProofTypeQueue>,
+}
+
+/// Queue of a single proving backend.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ProofTypeQueue {
+    /// The proof type identifier (e.g., "reth-zisk").
+    pub proof_type: ProofType,
+    /// Proofs waiting for the backend, oldest first.
+    pub queued: Vec<QueuedProof>,
+    /// Proof currently being generated, if any.
+    pub running: Option<QueuedProof>,
+}
+
+/// A proof waiting for or running on a proving backend.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct QueuedProof {
+    /// The tree-hash root of the `NewPayloadRequest`.
+    pub new_payload_request_root: Hash256,
+    /// Number of the block being proven.
+    pub block_number: u64,
+    /// Milliseconds since the proof was queued, or since proving started if running.
+    pub elapsed_ms: u64,
+}
+
+/// Response for `POST /admin/drain`, `POST /admin/resume` and `GET /health/ready`.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct DrainStatus {
+    /// Whether new proof requests are rejected.
+    pub draining: bool,
+    /// Number of proofs still queued or running.
+    pub in_flight: usize,
+}
+
+/// Response for `POST /admin/switchover/{proof_type}`.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SwitchoverResponse {
+    /// Proof type whose ere-servers were swapped.
+    pub proof_type: ProofType,
+    /// Endpoint of the ere-server now serving new proofs.
+    pub endpoint: String,
+    /// Docker image of the ere-server now serving new proofs, if configured.
+    pub image: Option<String>,
+}
+
+/// Response for `GET /health?deep=true`.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct HealthResponse {
+    /// Whether all checks passed.
+    pub healthy: bool,
+    /// Result of every dependency check.
+    pub checks: Vec<HealthCheck>,
+}
+
+/// Result of a single dependency check.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct HealthCheck {
+    /// Name of the checked dependency, e.g. `el`, `ere/reth-zisk` or `disk/record_dir`.
+    pub name: String,
+    /// Whether the dependency is reachable and healthy.
+    pub healthy: bool,
+    /// Error message if the check failed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Duration of the check in milliseconds.
+    pub duration_ms: u64,
+}
+
+/// Metadata of a proof request recorded to disk by the server for later replay.
+///
+/// Stored as `{new_payload_request_root}.json` next to the SSZ-encoded request body
+/// `{new_payload_request_root}.ssz`.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct RecordedProofRequest {
+    /// The tree-hash root of the recorded `NewPayloadRequest`.
+    pub new_payload_request_root: Hash256,
+    /// Proof types requested, in the order they were submitted.
+    pub proof_types: Vec<ProofType>,
+    /// Unix timestamp in milliseconds when the request was received.
+    pub received_at_ms: u64,
+}
+
+/// Outcome of a single proof attempt, appended by the server to its results file as one JSON
+/// line for performance tracking across runs.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ProofResultRecord {
+    /// Free-form label identifying the run, e.g. the machine or server version under test.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// Version of the server that produced the record.
+    pub server_version: String,
+    /// The tree-hash root of the proven `NewPayloadRequest`.
+    pub new_payload_request_root: Hash256,
+    /// Hash of the proven block.
+    pub block_hash: Hash256,
+    /// Number of the proven block.
+    pub block_number: u64,
+    /// Gas used by the proven block.
+    pub gas_used: u64,
+    /// Proof type.
+    pub proof_type: ProofType,
+    /// Failure reason, `None` if the proof was generated successfully.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failure_reason: Option<FailureReason>,
+    /// Wall-clock proving time in milliseconds.
+    pub proving_time_ms: u64,
+    /// Proof size in bytes, `0` on failure.
+    pub proof_size: u64,
+    /// Unix timestamp in milliseconds when proving finished.
+    pub completed_at_ms: u64,
+    /// Number of retries after transient backend failures.
+    #[serde(default)]
+    pub retries: u32,
+    /// Number of generated proofs that failed self-verification, each proven once more.
+    #[serde(default)]
+    pub self_verify_failures: u32,
+}
+
 impl ProofStatus {
     /// Returns `true` if proof status is `ProofStatus::Valid`:
     pub fn is_valid(&self) -> bool {
@@ -246,10 +515,19 @@ pub enum FailureReason {
     WitnessTimeout,
     /// Proof generation did not complete within the configured timeout.
     ProvingTimeout,
-    /// A general error occurred during proving.
+    /// A general error occurred during proving, e.g. the guest rejected the block.
     ProvingError,
     /// An internal error occurred.
     InternalError,
+    /// The guest input could not be built from the request and witness.
+    InvalidInput,
+    /// The proving backend could not be reached (e.g. ere-server container down).
+    BackendUnavailable,
+    /// The host or GPU did not have the configured minimum of available memory to start proving
+    /// within the proof timeout.
+    InsufficientMemory,
+    /// The block was older than the configured maximum age when proving would have started.
+    BlockTooOld,
 }
 
 /// Custom serde for comma-separated `Vec<ProofType>` in query strings.
@@ -0,0 +1,90 @@
+//! Public values of execution proofs.
+//!
+//! The stateless validator guests commit the SHA-256 of their encoded output, the root of the
+//! validated `NewPayloadRequest` and whether it was valid, as 32 bytes of public values. zkVMs
+//! with fixed size public values pad them with zeros.
+
+use ere_codec::Encode;
+use ere_guests_stateless_validator_common::guest;
+use lighthouse_types::Hash256;
+use sha2::{Digest, Sha256};
+
+/// Output of a stateless validator guest, committed to by the public values of a proof.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct StatelessValidatorOutput {
+    /// Root of the validated `NewPayloadRequest`.
+    pub new_payload_request_root: Hash256,
+    /// Whether the `NewPayloadRequest` passed stateless validation.
+    pub successful_validation: bool,
+}
+
+impl StatelessValidatorOutput {
+    /// Returns the 32 bytes of public values a guest commits for this output.
+    pub fn public_values(&self) -> [u8; 32] {
+        let output = guest::StatelessValidatorOutput::new(
+            self.new_payload_request_root.0,
+            self.successful_validation,
+        );
+        let encoded = output
+            .encode_to_vec()
+            .expect("stateless validator output is encodable");
+        Sha256::digest(encoded).into()
+    }
+
+    /// Decodes the public values of a proof of the `NewPayloadRequest` with
+    /// `new_payload_request_root`, `None` if they commit to any other output.
+    pub fn decode(public_values: &[u8], new_payload_request_root: Hash256) -> Option<Self> {
+        let (hash, padding) = public_values.split_at_checked(32)?;
+        if padding.iter().any(|byte| *byte != 0) {
+            return None;
+        }
+        [true, false]
+            .into_iter()
+            .map(|successful_validation| Self {
+                new_payload_request_root,
+                successful_validation,
+            })
+            .find(|output| output.public_values() == hash)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lighthouse_types::Hash256;
+
+    use super::StatelessValidatorOutput;
+
+    #[test]
+    fn test_decode_public_values() {
+        let root = Hash256::repeat_byte(1);
+        let valid = StatelessValidatorOutput {
+            new_payload_request_root: root,
+            successful_validation: true,
+        };
+        let invalid = StatelessValidatorOutput {
+            successful_validation: false,
+            ..valid
+        };
+
+        assert_eq!(
+            StatelessValidatorOutput::decode(&valid.public_values(), root),
+            Some(valid)
+        );
+        assert_eq!(
+            StatelessValidatorOutput::decode(&invalid.public_values(), root),
+            Some(invalid)
+        );
+
+        let mut padded = valid.public_values().to_vec();
+        padded.resize(64, 0);
+        assert_eq!(StatelessValidatorOutput::decode(&padded, root), Some(valid));
+        padded[63] = 1;
+        assert_eq!(StatelessValidatorOutput::decode(&padded, root), None);
+
+        let other_root = Hash256::repeat_byte(2);
+        assert_eq!(
+            StatelessValidatorOutput::decode(&valid.public_values(), other_root),
+            None
+        );
+    }
+}